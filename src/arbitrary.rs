@@ -0,0 +1,173 @@
+//! Implementations of [`Arbitrary`] for fuzzing.
+//!
+//! All generated values are built from strings that only contain valid characters for their
+//! component and are then parsed, so they will always pass validation.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+#[cfg(not(feature = "std"))]
+use core::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(feature = "std")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::authority::{Authority, Host, IPV4_AND_REGISTERED_NAME_CHAR_MAP, USER_INFO_CHAR_MAP};
+use crate::fragment::{Fragment, FRAGMENT_CHAR_MAP};
+use crate::generate::{self, allowed_bytes, MAX_LENGTH, MAX_SEGMENTS};
+use crate::path::{Path, PATH_CHAR_MAP};
+use crate::query::{Query, QUERY_CHAR_MAP};
+use crate::scheme::{Scheme, SCHEME_CHAR_MAP};
+use crate::uri::URI;
+
+impl<'a> Arbitrary<'a> for Authority<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let user_info = if u.arbitrary()? {
+            let username = arbitrary_string(u, &USER_INFO_CHAR_MAP, b":")?;
+            let password = if u.arbitrary()? {
+                Some(arbitrary_string(u, &USER_INFO_CHAR_MAP, b"")?)
+            } else {
+                None
+            };
+            Some((username, password))
+        } else {
+            None
+        };
+        let host = Host::arbitrary(u)?;
+        let port = Option::<u16>::arbitrary(u)?;
+        Ok(generate::authority(user_info, &host, port))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Fragment<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let fragment = arbitrary_string(u, &FRAGMENT_CHAR_MAP, b"")?;
+        Ok(Fragment::try_from(fragment.as_str()).unwrap().into_owned())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Host<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.int_in_range(0..=3)? {
+            0 => Ok(Host::from(Ipv4Addr::from(<[u8; 4]>::arbitrary(u)?))),
+            1 => Ok(Host::from(Ipv6Addr::from(<[u8; 16]>::arbitrary(u)?))),
+            _ => {
+                let name = arbitrary_string(u, &IPV4_AND_REGISTERED_NAME_CHAR_MAP, b"")?;
+                Ok(Host::try_from(name.as_str()).unwrap().into_owned())
+            }
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for Path<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let absolute = u.arbitrary()?;
+        let segment_count = u.int_in_range(1..=MAX_SEGMENTS)?;
+        let segments = (0..segment_count)
+            .map(|_| arbitrary_string(u, &PATH_CHAR_MAP, b""))
+            .collect::<Result<Vec<String>>>()?;
+        Ok(generate::path(absolute, &segments))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Query<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let query = arbitrary_string(u, &QUERY_CHAR_MAP, b"")?;
+        Ok(Query::try_from(query.as_str()).unwrap().into_owned())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Scheme<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut scheme = String::new();
+        scheme.push(*u.choose(b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ")? as char);
+
+        let length = u.int_in_range(0..=MAX_LENGTH)?;
+
+        for _ in 0..length {
+            scheme.push(*u.choose(&allowed_bytes(&SCHEME_CHAR_MAP, b""))? as char);
+        }
+
+        Ok(Scheme::try_from(scheme.as_str()).unwrap().into_owned())
+    }
+}
+
+impl<'a> Arbitrary<'a> for URI<'static> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let scheme = Scheme::arbitrary(u)?;
+        let authority = Option::<Authority>::arbitrary(u)?;
+        let path = Path::arbitrary(u)?;
+        let query = Option::<Query>::arbitrary(u)?;
+        let fragment = Option::<Fragment>::arbitrary(u)?;
+        Ok(generate::uri(scheme, authority, path, query, fragment))
+    }
+}
+
+/// Generates a string of bytes allowed by the given character map, excluding the given excluded
+/// bytes, that may also contain arbitrary percent-encoded bytes.
+fn arbitrary_string(
+    u: &mut Unstructured<'_>,
+    char_map: &[u8; 256],
+    excluded: &[u8],
+) -> Result<String> {
+    const HEX_DIGITS: &[u8; 22] = b"0123456789ABCDEFabcdef";
+
+    let allowed_bytes = allowed_bytes(char_map, excluded);
+    let length = u.int_in_range(0..=MAX_LENGTH)?;
+    let mut value = String::with_capacity(length);
+
+    for _ in 0..length {
+        if char_map[b'%' as usize] != 0 && u.ratio(1, 8)? {
+            value.push('%');
+            value.push(*u.choose(HEX_DIGITS)? as char);
+            value.push(*u.choose(HEX_DIGITS)? as char);
+        } else {
+            value.push(*u.choose(&allowed_bytes)? as char);
+        }
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary_round_trip() {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut data = [0; 512];
+
+        for _ in 0..1000 {
+            for byte in data.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *byte = state as u8;
+            }
+
+            let mut u = Unstructured::new(&data);
+            let uri = URI::arbitrary(&mut u).unwrap();
+            assert_eq!(URI::try_from(uri.to_string().as_str()).unwrap(), uri);
+
+            let authority = Authority::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                Authority::try_from(authority.to_string().as_str()).unwrap(),
+                authority
+            );
+
+            let host = Host::arbitrary(&mut u).unwrap();
+            assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+
+            let path = Path::arbitrary(&mut u).unwrap();
+            assert_eq!(Path::try_from(path.to_string().as_str()).unwrap(), path);
+
+            let query = Query::arbitrary(&mut u).unwrap();
+            assert_eq!(Query::try_from(query.as_str()).unwrap(), query);
+
+            let fragment = Fragment::arbitrary(&mut u).unwrap();
+            assert_eq!(Fragment::try_from(fragment.as_str()).unwrap(), fragment);
+        }
+    }
+}
@@ -22,25 +22,36 @@
 //! comes down to it just being too expensive to do a proper host comparison. To do so would require
 //! conversion to [`IpAddr`], which in the case of [`Ipv6Addr`] can be expensive.
 
-use std::borrow::Cow;
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter, Write};
+use core::hash::{Hash, Hasher};
+use core::mem;
+#[cfg(not(feature = "std"))]
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::ops::Deref;
+use core::str;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter, Write};
-use std::hash::{Hash, Hasher};
-use std::mem;
+#[cfg(feature = "std")]
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
-use std::ops::Deref;
-use std::str;
 
+use crate::scheme::Scheme;
+#[cfg(feature = "idna")]
+use crate::utility::percent_decode;
 use crate::utility::{
-    get_percent_encoded_value, normalize_string, percent_encoded_equality, percent_encoded_hash,
-    UNRESERVED_CHAR_MAP,
+    decode_percent_encoded, encode_percent_encoded, get_percent_encoded_value, normalize_string,
+    percent_decoded_constant_time_equality, percent_encoded_cmp, percent_encoded_equality,
+    percent_encoded_hash, UNRESERVED_CHAR_MAP,
 };
 
 /// A map of byte characters that determines if a character is a valid IPv4 or registered name
 /// character.
 #[rustfmt::skip]
-const IPV4_AND_REGISTERED_NAME_CHAR_MAP: [u8; 256] = [
+pub(crate) const IPV4_AND_REGISTERED_NAME_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -84,7 +95,7 @@ const IPV_FUTURE_CHAR_MAP: [u8; 256] = [
 
 /// A map of byte characters that determines if a character is a valid user information character.
 #[rustfmt::skip]
-const USER_INFO_CHAR_MAP: [u8; 256] = [
+pub(crate) const USER_INFO_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -104,12 +115,46 @@ const USER_INFO_CHAR_MAP: [u8; 256] = [
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
 ];
 
+/// A map of byte characters that determines if a character is a valid IPv6 zone identifier
+/// character.
+#[rustfmt::skip]
+const ZONE_IDENTIFIER_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0,    0,    0,    0,    0, b'%',    0,    0,    0,    0,    0,    0,    0, b'-', b'.',    0, // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9',    0,    0,    0,    0,    0,    0, // 3
+    0, b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
 /// The authority component as defined in
 /// [[RFC3986, Section 3.2](https://tools.ietf.org/html/rfc3986#section-3.2)].
 ///
 /// Any conversions to a string will **not** hide the password component of the authority. Be
-/// careful if you decide to perform logging.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+/// careful if you decide to perform logging. To hide the user information, format the authority
+/// with the alternate flag (i.e. `"{:#}"`), which replaces it with `"***"`.
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use uriparse::Authority;
+///
+/// let authority = Authority::try_from("user:pass@example.com").unwrap();
+/// assert_eq!(format!("{}", authority), "user:pass@example.com");
+/// assert_eq!(format!("{:#}", authority), "***@example.com");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Authority<'authority> {
     /// The host component of the authority as defined in
     /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
@@ -129,12 +174,8 @@ pub struct Authority<'authority> {
 }
 
 impl<'authority> Authority<'authority> {
-    pub fn as_borrowed(&self) -> Authority {
-        let host = match &self.host {
-            Host::RegisteredName(name) => Host::RegisteredName(name.as_borrowed()),
-            Host::IPv4Address(ipv4) => Host::IPv4Address(*ipv4),
-            Host::IPv6Address(ipv6) => Host::IPv6Address(*ipv6),
-        };
+    pub fn as_borrowed(&self) -> Authority<'_> {
+        let host = self.host.as_borrowed();
         let password = self.password.as_ref().map(Password::as_borrowed);
         let username = self.username.as_ref().map(Username::as_borrowed);
 
@@ -146,6 +187,28 @@ impl<'authority> Authority<'authority> {
         }
     }
 
+    /// Returns whether the host and port of the authority are equal to those of another authority,
+    /// ignoring the user information.
+    ///
+    /// The hosts are compared as done by the [`Host`] equality function, so they are
+    /// case-insensitive, and the ports must both be absent or equal. This is consistent with
+    /// [`Authority::hash_host_port`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("user@example.com:80").unwrap();
+    /// assert!(authority.eq_host_port(&Authority::try_from("EXAMPLE.com:80").unwrap()));
+    /// assert!(!authority.eq_host_port(&Authority::try_from("example.com").unwrap()));
+    /// ```
+    pub fn eq_host_port(&self, other: &Authority) -> bool {
+        self.host == other.host && self.port == other.port
+    }
+
     /// Constructs a new [`Authority`] from the individual parts: username, password, host, and
     /// port.
     ///
@@ -264,6 +327,19 @@ impl<'authority> Authority<'authority> {
         self.username.is_some()
     }
 
+    /// Feeds the host and port of the authority into the given hasher, ignoring the user
+    /// information.
+    ///
+    /// This is consistent with [`Authority::eq_host_port`], so it can be used to key a hash map on
+    /// just the host and port, e.g. for connection pooling.
+    pub fn hash_host_port<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.host.hash(state);
+        self.port.hash(state);
+    }
+
     /// The host component of the authority as defined in
     /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
     ///
@@ -295,11 +371,7 @@ impl<'authority> Authority<'authority> {
     pub fn into_owned(self) -> Authority<'static> {
         let password = self.password.map(Password::into_owned);
         let username = self.username.map(Username::into_owned);
-        let host = match self.host {
-            Host::RegisteredName(name) => Host::RegisteredName(name.into_owned()),
-            Host::IPv4Address(ipv4) => Host::IPv4Address(ipv4),
-            Host::IPv6Address(ipv6) => Host::IPv6Address(ipv6),
-        };
+        let host = self.host.into_owned();
 
         Authority {
             host,
@@ -497,6 +569,45 @@ impl<'authority> Authority<'authority> {
         self.host.normalize();
     }
 
+    /// Parses the authority from the given byte string, additionally enforcing the given policy.
+    ///
+    /// With the default policy, this is the same as [`Authority::try_from`]. Otherwise, the
+    /// authority is rejected with [`AuthorityError::UserInfoNotAllowed`] if it contains user
+    /// information that the policy forbids, or with [`AuthorityError::MissingHost`] if it has an
+    /// empty host that the policy requires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::{Authority, AuthorityError, AuthorityPolicy};
+    ///
+    /// let policy = AuthorityPolicy {
+    ///     forbid_user_info: true,
+    ///     ..AuthorityPolicy::default()
+    /// };
+    /// assert!(Authority::parse_with_policy(b"example.com", &policy).is_ok());
+    /// assert_eq!(
+    ///     Authority::parse_with_policy(b"user:pass@example.com", &policy),
+    ///     Err(AuthorityError::UserInfoNotAllowed)
+    /// );
+    /// ```
+    pub fn parse_with_policy(
+        value: &'authority [u8],
+        policy: &AuthorityPolicy,
+    ) -> Result<Authority<'authority>, AuthorityError> {
+        let authority = Authority::try_from(value)?;
+
+        if policy.forbid_user_info && authority.has_username() {
+            return Err(AuthorityError::UserInfoNotAllowed);
+        }
+
+        if policy.require_host && authority.host().is_empty() {
+            return Err(AuthorityError::MissingHost);
+        }
+
+        Ok(authority)
+    }
+
     /// The password component of the authority as defined in
     /// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
     ///
@@ -537,6 +648,28 @@ impl<'authority> Authority<'authority> {
         self.port
     }
 
+    /// The port to use when connecting to the authority.
+    ///
+    /// This is the explicit port if one was specified, otherwise it is the default port of the
+    /// given scheme (see [`Scheme::default_port`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Authority, Scheme};
+    ///
+    /// let authority = Authority::try_from("example.com:8080").unwrap();
+    /// assert_eq!(authority.port_or_default(&Scheme::HTTP), Some(8080));
+    ///
+    /// let authority = Authority::try_from("example.com").unwrap();
+    /// assert_eq!(authority.port_or_default(&Scheme::HTTP), Some(80));
+    /// ```
+    pub fn port_or_default(&self, scheme: &Scheme) -> Option<u16> {
+        self.port.or_else(|| scheme.default_port())
+    }
+
     /// Sets the host of the authority.
     ///
     /// An error will be returned if the conversion to a [`Host`] fails.
@@ -688,16 +821,103 @@ impl<'authority> Authority<'authority> {
     pub fn username(&self) -> Option<&Username<'authority>> {
         self.username.as_ref()
     }
+
+    /// The user information of the authority, consisting of the username and password.
+    ///
+    /// The user information will be `None` if there is no username.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("username:password@example.com").unwrap();
+    /// assert_eq!(authority.user_info().unwrap().to_string(), "username:password");
+    /// ```
+    pub fn user_info(&self) -> Option<UserInfo<'_>> {
+        self.username.as_ref().map(|username| UserInfo {
+            password: self.password.as_ref().map(Password::as_borrowed),
+            username: username.as_borrowed(),
+        })
+    }
+
+    /// Returns an owned copy of the authority with the port removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("user:pass@example.com:8080").unwrap();
+    /// assert_eq!(authority.without_port().to_string(), "user:pass@example.com");
+    /// ```
+    pub fn without_port(&self) -> Authority<'static> {
+        let mut authority = self.clone().into_owned();
+        authority.port = None;
+        authority
+    }
+
+    /// Returns an owned copy of the authority with the user information (i.e. the username and
+    /// password) removed.
+    ///
+    /// This is useful for logging since it ensures no credentials are included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("user:pass@example.com:8080").unwrap();
+    /// assert_eq!(authority.without_userinfo().to_string(), "example.com:8080");
+    /// ```
+    pub fn without_userinfo(&self) -> Authority<'static> {
+        let mut authority = self.clone().into_owned();
+        authority.password = None;
+        authority.username = None;
+        authority
+    }
+
+    /// Appends the authority to the given string.
+    ///
+    /// The authority is formatted with [`Display`] directly into the string, so no intermediate
+    /// string is allocated. The user information is never redacted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Authority;
+    ///
+    /// let authority = Authority::try_from("user:pass@example.com:8080").unwrap();
+    /// let mut string = String::from("//");
+    /// authority.write_to(&mut string);
+    /// assert_eq!(string, "//user:pass@example.com:8080");
+    /// ```
+    pub fn write_to(&self, out: &mut String) {
+        // Writing to a string cannot fail.
+        write!(out, "{}", self).unwrap();
+    }
 }
 
 impl Display for Authority<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         if let Some(ref username) = self.username {
-            username.fmt(formatter)?;
-
-            if let Some(ref password) = self.password {
-                formatter.write_char(':')?;
-                password.fmt(formatter)?;
+            if formatter.alternate() {
+                formatter.write_str("***")?;
+            } else {
+                username.fmt(formatter)?;
+
+                if let Some(ref password) = self.password {
+                    formatter.write_char(':')?;
+                    password.fmt(formatter)?;
+                }
             }
 
             formatter.write_char('@')?;
@@ -716,7 +936,9 @@ impl Display for Authority<'_> {
 
 impl<'authority> From<Authority<'authority>> for String {
     fn from(value: Authority<'authority>) -> String {
-        value.to_string()
+        let mut string = String::new();
+        value.write_to(&mut string);
+        string
     }
 }
 
@@ -724,7 +946,7 @@ impl<'authority> TryFrom<&'authority [u8]> for Authority<'authority> {
     type Error = AuthorityError;
 
     fn try_from(value: &'authority [u8]) -> Result<Self, Self::Error> {
-        let (authority, rest) = parse_authority(value)?;
+        let (authority, rest) = parse_authority(value).map_err(|(error, _)| error)?;
 
         if rest.is_empty() {
             Ok(authority)
@@ -748,6 +970,23 @@ impl<'authority> TryFrom<&'authority str> for Authority<'authority> {
     }
 }
 
+/// A policy for additional restrictions on an authority, as enforced by
+/// [`Authority::parse_with_policy`].
+///
+/// The default policy does not impose any restrictions beyond what
+/// [RFC3986](https://tools.ietf.org/html/rfc3986) requires.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct AuthorityPolicy {
+    /// Whether to reject authorities with user information (e.g. `"user:pass@example.com"`).
+    ///
+    /// Embedded credentials are deprecated for many schemes, and are commonly used to disguise
+    /// the actual host to users (e.g. `"https://trusted.com@evil.com"`).
+    pub forbid_user_info: bool,
+
+    /// Whether to reject authorities with an empty host (e.g. `":8080"`).
+    pub require_host: bool,
+}
+
 /// The host component of the authority as defined in
 /// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)].
 ///
@@ -763,7 +1002,7 @@ impl<'authority> TryFrom<&'authority str> for Authority<'authority> {
 /// However, be aware that just because percent-encoding plays no role in equality checking does not
 /// mean that the host is normalized. If the host needs to be normalized, use the
 /// [`Host::normalize`] function.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Host<'host> {
     /// An IPv4 address. Based on the `std`'s implementation, leading zeros for octets are allowed
     /// for up to three digits. So for example, `"000.000.000.000"` is still considered a valid IPv4
@@ -773,6 +1012,16 @@ pub enum Host<'host> {
     /// An IPv6 address. This will always be encased in brackets (`'['` and `']'`).
     IPv6Address(Ipv6Addr),
 
+    /// An IPv6 address with a zone identifier as defined in
+    /// [[RFC6874](https://tools.ietf.org/html/rfc6874)] (e.g. `"[fe80::1%25eth0]"`). This will
+    /// always be encased in brackets (`'['` and `']'`), and the zone identifier will always be
+    /// preceded by `"%25"` (i.e. a percent-encoded `'%'`).
+    IPv6AddressWithZone(Ipv6Addr, ZoneIdentifier<'host>),
+
+    /// A future IP literal (e.g. `"[v1.fe80::a+en1]"`). This will always be encased in brackets
+    /// (`'['` and `']'`).
+    IPvFuture(IPvFuture<'host>),
+
     /// Any other host that does not follow the syntax of an IP address. This includes even hosts of
     /// the form `"999.999.999.999"`. One might expect this to produce an invalid IPv4 error, but
     /// the RFC states that it is a "first-match-wins" algorithm, and that host does not match the
@@ -782,105 +1031,140 @@ pub enum Host<'host> {
     RegisteredName(RegisteredName<'host>),
 }
 
+impl Host<'static> {
+    /// Constructs a registered name host from the given string by percent-encoding all bytes
+    /// that are not allowed in a registered name.
+    ///
+    /// The `'%'` character is always percent-encoded, so the value is treated as not containing
+    /// any percent-encodings. If the value is an IPv4 address (e.g. `"127.0.0.1"`), an IPv4 address
+    /// host is returned instead, since that is what its string representation would be parsed as.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::registered_name_from_unencoded("my host:80");
+    /// assert!(host.is_registered_name());
+    /// assert_eq!(host.to_string(), "my%20host%3A80");
+    ///
+    /// let host = Host::registered_name_from_unencoded("127.0.0.1");
+    /// assert!(host.is_ipv4_address());
+    /// assert!(host.is_loopback());
+    /// ```
+    pub fn registered_name_from_unencoded(value: &str) -> Host<'static> {
+        if let Ok(ipv4) = value.parse() {
+            return Host::IPv4Address(ipv4);
+        }
+
+        Host::RegisteredName(RegisteredName {
+            normalized: !value.bytes().any(|byte| byte.is_ascii_uppercase()),
+            registered_name: Cow::Owned(encode_percent_encoded(
+                value,
+                &IPV4_AND_REGISTERED_NAME_CHAR_MAP,
+            )),
+        })
+    }
+}
+
 impl Host<'_> {
     /// Returns a new host which is identical but has a lifetime tied to this host.
-    pub fn as_borrowed(&self) -> Host {
+    pub fn as_borrowed(&self) -> Host<'_> {
         use self::Host::*;
 
         match self {
             IPv4Address(ipv4) => IPv4Address(*ipv4),
             IPv6Address(ipv6) => IPv6Address(*ipv6),
+            IPv6AddressWithZone(ipv6, zone) => IPv6AddressWithZone(*ipv6, zone.as_borrowed()),
+            IPvFuture(ipvfuture) => IPvFuture(ipvfuture.as_borrowed()),
             RegisteredName(name) => RegisteredName(name.as_borrowed()),
         }
     }
 
-    /// Converts the [`Host`] into an owned copy.
+    /// Returns the IP address of the host if it is an IPv4 or IPv6 address.
     ///
-    /// If you construct the host from a source with a non-static lifetime, you may run into
-    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
-    /// that the returned value has a static lifetime.
-    ///
-    /// This is different from just cloning. Cloning the host will just copy the references, and
-    /// thus the lifetime will remain the same.
-    pub fn into_owned(self) -> Host<'static> {
-        use self::Host::*;
-
-        match self {
-            IPv4Address(ipv4) => IPv4Address(ipv4),
-            IPv6Address(ipv6) => IPv6Address(ipv6),
-            RegisteredName(name) => RegisteredName(name.into_owned()),
-        }
-    }
-
-    /// Returns whether the host is an IPv4 address.
+    /// Any zone identifier of an IPv6 address is not included.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
+    /// use std::net::{IpAddr, Ipv4Addr};
     ///
     /// use uriparse::Host;
     ///
-    /// let host = Host::try_from("192.168.1.1").unwrap();
-    /// assert!(host.is_ipv4_address());
+    /// let host = Host::try_from("127.0.0.1").unwrap();
+    /// assert_eq!(host.as_ip_addr(), Some(IpAddr::V4(Ipv4Addr::LOCALHOST)));
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert_eq!(host.as_ip_addr(), None);
     /// ```
-    pub fn is_ipv4_address(&self) -> bool {
+    pub fn as_ip_addr(&self) -> Option<IpAddr> {
+        use self::Host::*;
+
         match self {
-            Host::IPv4Address(_) => true,
-            _ => false,
+            IPv4Address(address) => Some(IpAddr::V4(*address)),
+            IPv6Address(address) | IPv6AddressWithZone(address, _) => Some(IpAddr::V6(*address)),
+            IPvFuture(_) | RegisteredName(_) => None,
         }
     }
 
-    /// Returns whether the host is an IPv6 address.
+    /// Returns the IPv4 address of the host if it is an IPv4 address.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
+    /// use std::net::Ipv4Addr;
     ///
     /// use uriparse::Host;
     ///
+    /// let host = Host::try_from("127.0.0.1").unwrap();
+    /// assert_eq!(host.as_ipv4(), Some(Ipv4Addr::LOCALHOST));
+    ///
     /// let host = Host::try_from("[::1]").unwrap();
-    /// assert!(host.is_ipv6_address());
+    /// assert_eq!(host.as_ipv4(), None);
     /// ```
-    pub fn is_ipv6_address(&self) -> bool {
+    pub fn as_ipv4(&self) -> Option<Ipv4Addr> {
         match self {
-            Host::IPv6Address(_) => true,
-            _ => false,
+            Host::IPv4Address(address) => Some(*address),
+            _ => None,
         }
     }
 
-    /// Returns whether the host is normalized.
-    ///
-    /// IPv4 and IPv6 hosts will always be normalized. Registered names are considered normalized
-    /// if all characters are lowercase, no bytes that are in the unreserved character set are
-    /// percent-encoded, and all alphabetical characters in percent-encodings are uppercase.
+    /// Returns the IPv6 address of the host if it is an IPv6 address.
     ///
-    /// This function runs in constant-time.
+    /// Any zone identifier of the address is not included. Addresses written with an embedded IPv4
+    /// address (e.g. `"[::ffff:192.168.0.1]"`) are returned as the equivalent IPv6 address.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
+    /// use std::net::Ipv6Addr;
     ///
     /// use uriparse::Host;
     ///
-    /// let host = Host::try_from("192.168.1.1").unwrap();
-    /// assert!(host.is_normalized());
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.as_ipv6(), Some(Ipv6Addr::LOCALHOST));
     ///
-    /// let mut host = Host::try_from("EXAMPLE.COM").unwrap();
-    /// assert!(!host.is_normalized());
-    /// host.normalize();
-    /// assert!(host.is_normalized());
+    /// let host = Host::try_from("[::ffff:192.168.0.1]").unwrap();
+    /// assert_eq!(
+    ///     host.as_ipv6(),
+    ///     Some(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0xc0a8, 0x0001))
+    /// );
+    ///
+    /// let host = Host::try_from("127.0.0.1").unwrap();
+    /// assert_eq!(host.as_ipv6(), None);
     /// ```
-    pub fn is_normalized(&self) -> bool {
+    pub fn as_ipv6(&self) -> Option<Ipv6Addr> {
         match self {
-            Host::RegisteredName(name) => name.is_normalized(),
-            _ => true,
+            Host::IPv6Address(address) | Host::IPv6AddressWithZone(address, _) => Some(*address),
+            _ => None,
         }
     }
 
-    /// Returns whether the host is a registered name.
+    /// Returns the future IP literal of the host if it is one.
     ///
     /// # Examples
     ///
@@ -889,25 +1173,25 @@ impl Host<'_> {
     ///
     /// use uriparse::Host;
     ///
-    /// let host = Host::try_from("example.com").unwrap();
-    /// assert!(host.is_registered_name());
+    /// let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+    /// assert_eq!(host.as_ipvfuture().unwrap(), "v1.fe80::a+en1");
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert!(host.as_ipvfuture().is_none());
     /// ```
-    pub fn is_registered_name(&self) -> bool {
+    pub fn as_ipvfuture(&self) -> Option<&IPvFuture<'_>> {
         match self {
-            Host::RegisteredName(_) => true,
-            _ => false,
+            Host::IPvFuture(ipvfuture) => Some(ipvfuture),
+            _ => None,
         }
     }
 
-    /// Normalizes the host such that all characters are lowercase, no bytes that are in the
-    /// unreserved character set are percent-encoded, and all alphabetical characters in
-    /// percent-encodings are uppercase.
-    ///
-    /// If the host is already normalized, the function will return immediately. Otherwise, if
-    /// the host is not owned, this function will perform an allocation to clone it. The
-    /// normalization itself though, is done in-place with no extra memory allocations required.
+    /// Returns whether the two hosts are equal, treating a single trailing `'.'` on registered names
+    /// as insignificant.
     ///
-    /// IPv4 and IPv6 hosts are always considered normalized.
+    /// A fully qualified domain name such as `"example.com."` usually refers to the same host as
+    /// `"example.com"`. Equality of the host itself is exact, so this function can be used when the
+    /// difference does not matter. All other hosts are compared as usual.
     ///
     /// # Examples
     ///
@@ -916,214 +1200,1135 @@ impl Host<'_> {
     ///
     /// use uriparse::Host;
     ///
-    /// let mut host = Host::try_from("192.168.1.1").unwrap();
-    /// host.normalize();
-    /// assert_eq!(host.to_string(), "192.168.1.1");
-    ///
-    /// let mut host = Host::try_from("%ff%41").unwrap();
-    /// assert_eq!(host.to_string(), "%ff%41");
-    /// host.normalize();
-    /// assert_eq!(host.to_string(), "%FFA");
+    /// let host = Host::try_from("example.com").unwrap();
+    /// let fqdn = Host::try_from("EXAMPLE.com.").unwrap();
+    /// assert!(host.eq_fqdn(&fqdn));
+    /// assert_ne!(host, fqdn);
     /// ```
-    pub fn normalize(&mut self) {
-        if let Host::RegisteredName(name) = self {
-            name.normalize()
+    pub fn eq_fqdn(&self, other: &Host) -> bool {
+        fn strip_root<'name>(name: &'name RegisteredName<'_>) -> &'name [u8] {
+            let name = name.as_str();
+            name.strip_suffix('.').unwrap_or(name).as_bytes()
+        }
+
+        match (self, other) {
+            (Host::RegisteredName(name), Host::RegisteredName(other_name)) => {
+                percent_encoded_equality(strip_root(name), strip_root(other_name), false)
+            }
+            _ => self == other,
         }
     }
-}
 
-impl Display for Host<'_> {
-    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+    /// Converts the [`Host`] into an owned copy.
+    ///
+    /// If you construct the host from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the host will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Host<'static> {
         use self::Host::*;
 
         match self {
-            IPv4Address(address) => address.fmt(formatter),
-            IPv6Address(address) => {
-                formatter.write_char('[')?;
-                address.fmt(formatter)?;
-                formatter.write_char(']')
-            }
-            RegisteredName(name) => formatter.write_str(name.as_str()),
+            IPv4Address(ipv4) => IPv4Address(ipv4),
+            IPv6Address(ipv6) => IPv6Address(ipv6),
+            IPv6AddressWithZone(ipv6, zone) => IPv6AddressWithZone(ipv6, zone.into_owned()),
+            IPvFuture(ipvfuture) => IPvFuture(ipvfuture.into_owned()),
+            RegisteredName(name) => RegisteredName(name.into_owned()),
         }
     }
-}
 
-impl<'host> From<Host<'host>> for String {
-    fn from(value: Host<'host>) -> String {
-        value.to_string()
+    /// Returns whether the host is empty, i.e. an empty registered name.
+    ///
+    /// An empty host is different from having no authority at all. For example,
+    /// `"file:///etc/hosts"` has an authority with an empty host, while `"file:/etc/hosts"` has no
+    /// authority. An empty host does not produce any output when formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("").unwrap().is_empty());
+    /// assert!(!Host::try_from("example.com").unwrap().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Host::RegisteredName(name) if name.as_str().is_empty())
     }
-}
 
-impl From<IpAddr> for Host<'static> {
-    fn from(value: IpAddr) -> Self {
-        match value {
-            IpAddr::V4(address) => Host::IPv4Address(address),
+    /// Returns whether the host is an IPv4 address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("192.168.1.1").unwrap();
+    /// assert!(host.is_ipv4_address());
+    /// ```
+    pub fn is_ipv4_address(&self) -> bool {
+        matches!(self, Host::IPv4Address(_))
+    }
+
+    /// Returns whether the host is an IPv6 address, with or without a zone identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert!(host.is_ipv6_address());
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert!(host.is_ipv6_address());
+    /// ```
+    pub fn is_ipv6_address(&self) -> bool {
+        matches!(self, Host::IPv6Address(_) | Host::IPv6AddressWithZone(_, _))
+    }
+
+    /// Returns whether the host is a future IP literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+    /// assert!(host.is_ipvfuture());
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert!(!host.is_ipvfuture());
+    /// ```
+    pub fn is_ipvfuture(&self) -> bool {
+        matches!(self, Host::IPvFuture(_))
+    }
+
+    /// Returns whether the host is a loopback address.
+    ///
+    /// This is true for IPv4 addresses in `127.0.0.0/8`, the IPv6 address `::1`, and the registered
+    /// name `"localhost"` (case-insensitive). IPv4-mapped IPv6 addresses (e.g.
+    /// `"[::ffff:127.0.0.1]"`) are checked as the IPv4 address they map to. No other registered
+    /// names are considered to be loopback addresses as that would require name resolution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("127.0.0.1").unwrap().is_loopback());
+    /// assert!(Host::try_from("[::1]").unwrap().is_loopback());
+    /// assert!(Host::try_from("LOCALHOST").unwrap().is_loopback());
+    /// assert!(!Host::try_from("example.com").unwrap().is_loopback());
+    /// ```
+    pub fn is_loopback(&self) -> bool {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(address) => address.is_loopback(),
+            IPv6Address(address) | IPv6AddressWithZone(address, _) => {
+                match address.to_ipv4_mapped() {
+                    Some(address) => address.is_loopback(),
+                    None => address.is_loopback(),
+                }
+            }
+            IPvFuture(_) => false,
+            RegisteredName(name) => name == "localhost",
+        }
+    }
+
+    /// Returns whether the host is normalized.
+    ///
+    /// IPv4 and IPv6 hosts will always be normalized. Future IP literals are considered normalized
+    /// if all characters are lowercase. Registered names are considered normalized if all
+    /// characters are lowercase, no bytes that are in the unreserved character set are
+    /// percent-encoded, and all alphabetical characters in percent-encodings are uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("192.168.1.1").unwrap();
+    /// assert!(host.is_normalized());
+    ///
+    /// let mut host = Host::try_from("EXAMPLE.COM").unwrap();
+    /// assert!(!host.is_normalized());
+    /// host.normalize();
+    /// assert!(host.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        match self {
+            Host::IPv6AddressWithZone(_, zone) => zone.is_normalized(),
+            Host::IPvFuture(ipvfuture) => ipvfuture.is_normalized(),
+            Host::RegisteredName(name) => name.is_normalized(),
+            _ => true,
+        }
+    }
+
+    /// Returns whether the host is a private or link-local IP address.
+    ///
+    /// This is true for IPv4 addresses in `10.0.0.0/8`, `172.16.0.0/12`, `192.168.0.0/16`, and
+    /// `169.254.0.0/16`, and for IPv6 addresses in `fc00::/7` and `fe80::/10`. IPv4-mapped IPv6
+    /// addresses (e.g. `"[::ffff:10.0.0.1]"`) are checked as the IPv4 address they map to. Future
+    /// IP literals and registered names are never considered private.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// assert!(Host::try_from("192.168.1.1").unwrap().is_private());
+    /// assert!(Host::try_from("[fd00::1]").unwrap().is_private());
+    /// assert!(!Host::try_from("8.8.8.8").unwrap().is_private());
+    /// assert!(!Host::try_from("localhost").unwrap().is_private());
+    /// ```
+    pub fn is_private(&self) -> bool {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(address) => address.is_private() || address.is_link_local(),
+            IPv6Address(address) | IPv6AddressWithZone(address, _) => {
+                if let Some(address) = address.to_ipv4_mapped() {
+                    return address.is_private() || address.is_link_local();
+                }
+
+                let first_segment = address.segments()[0];
+                let unique_local = first_segment & 0xfe00 == 0xfc00;
+                let link_local = first_segment & 0xffc0 == 0xfe80;
+                unique_local || link_local
+            }
+            IPvFuture(_) | RegisteredName(_) => false,
+        }
+    }
+
+    /// Returns whether the host is a registered name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert!(host.is_registered_name());
+    /// ```
+    pub fn is_registered_name(&self) -> bool {
+        matches!(self, Host::RegisteredName(_))
+    }
+
+    /// Normalizes the host such that all characters are lowercase, no bytes that are in the
+    /// unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
+    ///
+    /// If the host is already normalized, the function will return immediately. Otherwise, if
+    /// the host is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// IPv4 and IPv6 hosts are always considered normalized. They are stored in their parsed form
+    /// and IPv6 addresses are always serialized in the canonical form defined in
+    /// [[RFC5952, Section 4](https://tools.ietf.org/html/rfc5952#section-4)], that is, with
+    /// lowercase hexadecimal digits, no leading zeros, and the longest run of zero groups
+    /// compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let mut host = Host::try_from("192.168.1.1").unwrap();
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "192.168.1.1");
+    ///
+    /// let mut host = Host::try_from("%ff%41").unwrap();
+    /// assert_eq!(host.to_string(), "%ff%41");
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "%FFa");
+    /// ```
+    pub fn normalize(&mut self) {
+        match self {
+            Host::IPv6AddressWithZone(_, zone) => zone.normalize(),
+            Host::IPvFuture(ipvfuture) => ipvfuture.normalize(),
+            Host::RegisteredName(name) => name.normalize(),
+            _ => (),
+        }
+    }
+
+    /// Converts the host into its ASCII form by applying the IDNA ToASCII operation as defined in
+    /// [[UTS #46](https://www.unicode.org/reports/tr46/#ToASCII)] to registered names.
+    ///
+    /// The registered name is percent-decoded before conversion, since that is the only way to
+    /// represent non-ASCII characters within a URI. IP literals are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("m%C3%BCnchen.de").unwrap();
+    /// assert_eq!(host.to_ascii().unwrap().to_string(), "xn--mnchen-3ya.de");
+    /// ```
+    #[cfg(feature = "idna")]
+    pub fn to_ascii(&self) -> Result<Host<'static>, IDNAError> {
+        match self {
+            Host::RegisteredName(name) => {
+                let decoded =
+                    percent_decode(name.as_str().as_bytes()).expect("invalid registered name");
+                let decoded = str::from_utf8(&decoded).map_err(|_| IDNAError::InvalidUTF8)?;
+                let ascii = idna::domain_to_ascii(decoded).map_err(|_| IDNAError::InvalidDomain)?;
+                let host = Host::try_from(ascii.as_str()).map_err(|_| IDNAError::InvalidDomain)?;
+                Ok(host.into_owned())
+            }
+            host => Ok(host.clone().into_owned()),
+        }
+    }
+
+    /// Returns the string representation of the host, with IP literals enclosed in brackets.
+    ///
+    /// This is the same as the [`Display`] representation, as used in an authority. Registered
+    /// names and IPv4 addresses are never enclosed in brackets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.to_string_bracketed(), "[::1]");
+    ///
+    /// let host = Host::try_from("127.0.0.1").unwrap();
+    /// assert_eq!(host.to_string_bracketed(), "127.0.0.1");
+    /// ```
+    pub fn to_string_bracketed(&self) -> String {
+        self.to_string()
+    }
+
+    /// Returns the string representation of the host, without the brackets enclosing IP literals.
+    ///
+    /// This is useful when the address is needed on its own, such as in a configuration file. The
+    /// zone identifier of an IPv6 address, if any, is percent-decoded and preceded by a plain `'%'`
+    /// as in the textual form of
+    /// [[RFC4007, Section 11](https://tools.ietf.org/html/rfc4007#section-11)] (e.g.
+    /// `"fe80::1%eth0"`). Registered names and IPv4 addresses are returned as they would be by
+    /// [`Host::to_string_bracketed`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.to_string_unbracketed(), "::1");
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert_eq!(host.to_string_unbracketed(), "fe80::1%eth0");
+    ///
+    /// let host = Host::try_from("example.com").unwrap();
+    /// assert_eq!(host.to_string_unbracketed(), "example.com");
+    /// ```
+    pub fn to_string_unbracketed(&self) -> String {
+        use self::Host::*;
+
+        match self {
+            IPv6Address(address) => address.to_string(),
+            IPv6AddressWithZone(address, zone) => {
+                format!("{}%{}", address, decode_percent_encoded(zone.as_str()))
+            }
+            IPvFuture(ipvfuture) => ipvfuture.as_str().to_string(),
+            host => host.to_string(),
+        }
+    }
+
+    /// Converts the host into its Unicode form by applying the IDNA ToUnicode operation as
+    /// defined in [[UTS #46](https://www.unicode.org/reports/tr46/#ToUnicode)] to registered
+    /// names.
+    ///
+    /// This is the inverse of [`Host::to_ascii`]. Since the result may contain characters that are
+    /// not valid in a URI, it is returned as a string. IP literals are returned in their usual
+    /// string representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("xn--mnchen-3ya.de").unwrap();
+    /// assert_eq!(host.to_unicode().unwrap(), "münchen.de");
+    /// ```
+    #[cfg(feature = "idna")]
+    pub fn to_unicode(&self) -> Result<String, IDNAError> {
+        match self {
+            Host::RegisteredName(name) => {
+                let decoded =
+                    percent_decode(name.as_str().as_bytes()).expect("invalid registered name");
+                let decoded = str::from_utf8(&decoded).map_err(|_| IDNAError::InvalidUTF8)?;
+
+                match idna::domain_to_unicode(decoded) {
+                    (unicode, Ok(())) => Ok(unicode),
+                    (_, Err(_)) => Err(IDNAError::InvalidDomain),
+                }
+            }
+            host => Ok(host.to_string()),
+        }
+    }
+
+    /// Returns the zone identifier of the host if it is an IPv6 address with a zone identifier.
+    ///
+    /// The returned zone identifier does not include the preceding `"%25"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+    /// assert_eq!(host.zone_identifier(), Some("eth0"));
+    ///
+    /// let host = Host::try_from("[::1]").unwrap();
+    /// assert_eq!(host.zone_identifier(), None);
+    /// ```
+    pub fn zone_identifier(&self) -> Option<&str> {
+        match self {
+            Host::IPv6AddressWithZone(_, zone) => Some(zone.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Appends the host to the given string.
+    ///
+    /// The host is formatted with [`Display`] directly into the string, so no intermediate string
+    /// is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let mut string = String::new();
+    /// Host::try_from("[::1]").unwrap().write_to(&mut string);
+    /// assert_eq!(string, "[::1]");
+    /// ```
+    pub fn write_to(&self, out: &mut String) {
+        // Writing to a string cannot fail.
+        write!(out, "{}", self).unwrap();
+    }
+}
+
+impl Display for Host<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::Host::*;
+
+        match self {
+            IPv4Address(address) => address.fmt(formatter),
+            IPv6Address(address) => {
+                formatter.write_char('[')?;
+                address.fmt(formatter)?;
+                formatter.write_char(']')
+            }
+            IPv6AddressWithZone(address, zone) => {
+                formatter.write_char('[')?;
+                address.fmt(formatter)?;
+                formatter.write_str("%25")?;
+                formatter.write_str(zone.as_str())?;
+                formatter.write_char(']')
+            }
+            IPvFuture(ipvfuture) => {
+                formatter.write_char('[')?;
+                formatter.write_str(ipvfuture.as_str())?;
+                formatter.write_char(']')
+            }
+            RegisteredName(name) => formatter.write_str(name.as_str()),
+        }
+    }
+}
+
+impl<'host> From<Host<'host>> for String {
+    fn from(value: Host<'host>) -> String {
+        let mut string = String::new();
+        value.write_to(&mut string);
+        string
+    }
+}
+
+impl From<IpAddr> for Host<'static> {
+    fn from(value: IpAddr) -> Self {
+        match value {
+            IpAddr::V4(address) => Host::IPv4Address(address),
             IpAddr::V6(address) => Host::IPv6Address(address),
         }
     }
 }
 
-impl From<Ipv4Addr> for Host<'static> {
-    fn from(value: Ipv4Addr) -> Self {
-        Host::IPv4Address(value)
+impl From<Ipv4Addr> for Host<'static> {
+    fn from(value: Ipv4Addr) -> Self {
+        Host::IPv4Address(value)
+    }
+}
+
+impl From<Ipv6Addr> for Host<'static> {
+    fn from(value: Ipv6Addr) -> Self {
+        Host::IPv6Address(value)
+    }
+}
+
+impl<'host> TryFrom<&'host [u8]> for Host<'host> {
+    type Error = HostError;
+
+    fn try_from(value: &'host [u8]) -> Result<Self, Self::Error> {
+        parse_host(value).map_err(|(error, _)| error)
+    }
+}
+
+impl<'host> TryFrom<&'host str> for Host<'host> {
+    type Error = HostError;
+
+    fn try_from(value: &'host str) -> Result<Self, Self::Error> {
+        Host::try_from(value.as_bytes())
+    }
+}
+
+/// A future IP literal as defined in
+/// [[RFC3986, Section 3.2.2](https://tools.ietf.org/html/rfc3986#section-3.2.2)] (e.g.
+/// `"[v1.fe80::a+en1]"`).
+///
+/// The literal consists of a hexadecimal version followed by a `'.'` and an address whose format
+/// depends on the version. Since no such versions are defined yet, the address is stored as is.
+///
+/// The future IP literal is case-insensitive, which is reflected in the equality and hash
+/// functions.
+///
+/// Note that the future IP literal does not include the brackets (`'['` and `']'`) surrounding it.
+#[derive(Clone, Debug)]
+pub struct IPvFuture<'ipvfuture> {
+    /// The internal future IP literal source that is either owned or borrowed.
+    ipvfuture: Cow<'ipvfuture, str>,
+
+    /// Whether the future IP literal is normalized.
+    normalized: bool,
+
+    /// The index of the `'.'` separating the version from the address.
+    separator_index: usize,
+}
+
+impl IPvFuture<'_> {
+    /// The address of the future IP literal, that is everything after the version and the `'.'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+    /// assert_eq!(host.as_ipvfuture().unwrap().address(), "fe80::a+en1");
+    /// ```
+    pub fn address(&self) -> &str {
+        &self.ipvfuture[self.separator_index + 1..]
+    }
+
+    /// Returns a new future IP literal which is identical but has a lifetime tied to this future
+    /// IP literal.
+    pub fn as_borrowed(&self) -> IPvFuture<'_> {
+        use self::Cow::*;
+
+        let ipvfuture = match &self.ipvfuture {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        IPvFuture {
+            ipvfuture: Cow::Borrowed(ipvfuture),
+            normalized: self.normalized,
+            separator_index: self.separator_index,
+        }
+    }
+
+    /// Returns a `str` representation of the future IP literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+    /// assert_eq!(host.as_ipvfuture().unwrap().as_str(), "v1.fe80::a+en1");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.ipvfuture
+    }
+
+    /// Converts the [`IPvFuture`] into an owned copy.
+    ///
+    /// If you construct the future IP literal from a source with a non-static lifetime, you may
+    /// run into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the future IP literal will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> IPvFuture<'static> {
+        IPvFuture {
+            ipvfuture: Cow::from(self.ipvfuture.into_owned()),
+            normalized: self.normalized,
+            separator_index: self.separator_index,
+        }
+    }
+
+    /// Returns whether the future IP literal is normalized.
+    ///
+    /// A normalized future IP literal will have all alphabetical characters lowercase.
+    ///
+    /// This function runs in constant-time.
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the future IP literal such that all alphabetical characters are lowercase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let mut host = Host::try_from("[V1.FE80::A+en1]").unwrap();
+    /// host.normalize();
+    /// assert_eq!(host.to_string(), "[v1.fe80::a+en1]");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            self.ipvfuture.to_mut().make_ascii_lowercase();
+            self.normalized = true;
+        }
+    }
+
+    /// The hexadecimal version of the future IP literal, not including the leading `'v'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Host;
+    ///
+    /// let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+    /// assert_eq!(host.as_ipvfuture().unwrap().version(), "1");
+    /// ```
+    pub fn version(&self) -> &str {
+        &self.ipvfuture[1..self.separator_index]
+    }
+}
+
+impl AsRef<[u8]> for IPvFuture<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.ipvfuture.as_bytes()
+    }
+}
+
+impl AsRef<str> for IPvFuture<'_> {
+    fn as_ref(&self) -> &str {
+        &self.ipvfuture
+    }
+}
+
+impl Deref for IPvFuture<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ipvfuture
+    }
+}
+
+impl Display for IPvFuture<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.ipvfuture)
+    }
+}
+
+impl Eq for IPvFuture<'_> {}
+
+impl<'ipvfuture> From<IPvFuture<'ipvfuture>> for String {
+    fn from(value: IPvFuture<'ipvfuture>) -> String {
+        value.to_string()
+    }
+}
+
+impl Hash for IPvFuture<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.ipvfuture.as_bytes(), state, false);
+    }
+}
+
+impl Ord for IPvFuture<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.ipvfuture.bytes().map(|byte| byte.to_ascii_lowercase());
+        let right = other
+            .ipvfuture
+            .bytes()
+            .map(|byte| byte.to_ascii_lowercase());
+        left.cmp(right)
+    }
+}
+
+impl PartialEq for IPvFuture<'_> {
+    fn eq(&self, other: &IPvFuture) -> bool {
+        self.ipvfuture.eq_ignore_ascii_case(&other.ipvfuture)
+    }
+}
+
+impl PartialEq<str> for IPvFuture<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.ipvfuture.eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'ipvfuture> PartialEq<IPvFuture<'ipvfuture>> for str {
+    fn eq(&self, other: &IPvFuture<'ipvfuture>) -> bool {
+        self.eq_ignore_ascii_case(&other.ipvfuture)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for IPvFuture<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.ipvfuture.eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'ipvfuture> PartialEq<IPvFuture<'ipvfuture>> for &str {
+    fn eq(&self, other: &IPvFuture<'ipvfuture>) -> bool {
+        self.eq_ignore_ascii_case(&other.ipvfuture)
+    }
+}
+
+impl PartialOrd for IPvFuture<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The password component of the authority as defined in
+/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+///
+/// Even though this library supports parsing the password from the user information, it should be
+/// noted that the format "username:password" is deprecated. Also, be careful logging this! The
+/// [`Debug`] implementation does not show the password, but [`Display`] and [`Password::as_str`]
+/// do.
+///
+/// The password is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"password"` and `"p%61ssword"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
+///
+/// Be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the password is normalized. If the password needs to be normalized, use the
+/// [`Password::normalize`] function.
+#[derive(Clone)]
+pub struct Password<'password> {
+    /// Whether the password is normalized.
+    normalized: bool,
+
+    /// The internal password source that is either owned or borrowed.
+    password: Cow<'password, str>,
+}
+
+impl Password<'_> {
+    /// Returns a new password which is identical but has a lifetime tied to this password.
+    pub fn as_borrowed(&self) -> Password<'_> {
+        use self::Cow::*;
+
+        let password = match &self.password {
+            Borrowed(borrowed) => *borrowed,
+            Owned(owned) => owned.as_str(),
+        };
+
+        Password {
+            normalized: self.normalized,
+            password: Cow::Borrowed(password),
+        }
+    }
+
+    /// Returns a `str` representation of the password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("password").unwrap();
+    /// assert_eq!(password, "password");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.password
+    }
+
+    /// Compares the password with another in constant time.
+    ///
+    /// Both passwords are fully percent-decoded before being compared, so unlike `==`, a
+    /// percent-encoded reserved character is equal to the character itself (e.g. `"a%3Ab"` and
+    /// `"a:b"`). The comparison only depends on the lengths of the decoded passwords, but the
+    /// decoding itself is not constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("p%40ss").unwrap();
+    /// assert!(password.ct_eq(&Password::try_from("p%40ss").unwrap()));
+    /// assert!(!password.ct_eq(&Password::try_from("p%40sS").unwrap()));
+    /// ```
+    pub fn ct_eq(&self, other: &Password) -> bool {
+        percent_decoded_constant_time_equality(self.password.as_bytes(), other.password.as_bytes())
+    }
+
+    /// Returns the password with all percent-encoded bytes decoded.
+    ///
+    /// If the password contains no percent-encodings, the returned value will borrow from the
+    /// password. Decoded byte sequences that are not valid UTF-8 are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("p%40ss").unwrap();
+    /// assert_eq!(password.decode(), "p@ss");
+    /// ```
+    pub fn decode(&self) -> Cow<'_, str> {
+        decode_percent_encoded(&self.password)
+    }
+
+    /// Converts the [`Password`] into an owned copy.
+    ///
+    /// If you construct the authority from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the password will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Password<'static> {
+        Password {
+            normalized: self.normalized,
+            password: Cow::from(self.password.into_owned()),
+        }
+    }
+
+    /// Returns whether the password is normalized.
+    ///
+    /// A normalized password will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// This function runs in constant-time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let password = Password::try_from("password").unwrap();
+    /// assert!(password.is_normalized());
+    ///
+    /// let mut password = Password::try_from("%ff%ff").unwrap();
+    /// assert!(!password.is_normalized());
+    /// password.normalize();
+    /// assert!(password.is_normalized());
+    /// ```
+    pub fn is_normalized(&self) -> bool {
+        self.normalized
+    }
+
+    /// Normalizes the password such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    ///
+    /// If the password is already normalized, the function will return immediately. Otherwise, if
+    /// the password is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Password;
+    ///
+    /// let mut password = Password::try_from("password").unwrap();
+    /// password.normalize();
+    /// assert_eq!(password, "password");
+    ///
+    /// let mut password = Password::try_from("%ff%41").unwrap();
+    /// assert_eq!(password, "%ff%41");
+    /// password.normalize();
+    /// assert_eq!(password, "%FFA");
+    /// ```
+    pub fn normalize(&mut self) {
+        if !self.normalized {
+            // Unsafe: Passwords must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.password.to_mut(), true) };
+            self.normalized = true;
+        }
+    }
+}
+
+impl AsRef<[u8]> for Password<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.password.as_bytes()
     }
 }
 
-impl From<Ipv6Addr> for Host<'static> {
-    fn from(value: Ipv6Addr) -> Self {
-        Host::IPv6Address(value)
+impl AsRef<str> for Password<'_> {
+    fn as_ref(&self) -> &str {
+        &self.password
     }
 }
 
-impl<'host> TryFrom<&'host [u8]> for Host<'host> {
-    type Error = HostError;
+impl fmt::Debug for Password<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.debug_tuple("Password").field(&"***").finish()
+    }
+}
 
-    fn try_from(value: &'host [u8]) -> Result<Self, Self::Error> {
-        if value.is_empty() {
-            let registered_name = RegisteredName {
-                normalized: true,
-                registered_name: Cow::from(""),
-            };
-            return Ok(Host::RegisteredName(registered_name));
-        }
+impl Deref for Password<'_> {
+    type Target = str;
 
-        match (value.get(0), value.get(value.len() - 1)) {
-            (Some(b'['), Some(b']')) => {
-                match value.get(1..3) {
-                    Some(&[prefix, version])
-                        if prefix.to_ascii_lowercase() == b'v' && version.is_ascii_hexdigit() =>
-                    {
-                        // IPvFuture
+    fn deref(&self) -> &Self::Target {
+        &self.password
+    }
+}
 
-                        let ipvfuture = &value[3..value.len() - 1];
+impl Display for Password<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(&self.password)
+    }
+}
 
-                        if check_ipvfuture(ipvfuture) {
-                            return Err(HostError::AddressMechanismNotSupported);
-                        } else {
-                            return Err(HostError::InvalidIPvFutureCharacter);
-                        }
-                    }
-                    _ => (),
-                }
+impl Eq for Password<'_> {}
 
-                // IPv6
+impl<'password> From<Password<'password>> for String {
+    fn from(value: Password<'password>) -> String {
+        value.to_string()
+    }
+}
 
-                let ipv6 = &value[1..value.len() - 1];
+impl Hash for Password<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        percent_encoded_hash(self.password.as_bytes(), state, true);
+    }
+}
 
-                if !check_ipv6(ipv6) {
-                    return Err(HostError::InvalidIPv6Character);
-                }
+impl Ord for Password<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.password.as_bytes(), other.password.as_bytes(), true)
+    }
+}
 
-                // Unsafe: The function above [`check_ipv6`] ensures this is valid ASCII-US.
-                let ipv6: Ipv6Addr = unsafe { str::from_utf8_unchecked(ipv6) }
-                    .parse()
-                    .map_err(|_| HostError::InvalidIPv6Format)?;
-                Ok(Host::IPv6Address(ipv6))
-            }
-            _ => {
-                let (valid, normalized) = check_ipv4_or_registered_name(value);
-
-                if valid {
-                    // Unsafe: The function above [`check_ipv4_or_registered_name`] ensures
-                    // this is valid ASCII-US.
-                    let value_string = unsafe { str::from_utf8_unchecked(value) };
-
-                    match value_string.parse() {
-                        Ok(ipv4) => Ok(Host::IPv4Address(ipv4)),
-                        Err(_) => Ok(Host::RegisteredName(RegisteredName {
-                            normalized,
-                            registered_name: Cow::from(value_string),
-                        })),
-                    }
-                } else {
-                    Err(HostError::InvalidIPv4OrRegisteredNameCharacter)
-                }
-            }
-        }
+impl PartialEq for Password<'_> {
+    fn eq(&self, other: &Password) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other.password.as_bytes(), true)
     }
 }
 
-impl<'host> TryFrom<&'host str> for Host<'host> {
-    type Error = HostError;
+impl PartialEq<[u8]> for Password<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other, true)
+    }
+}
 
-    fn try_from(value: &'host str) -> Result<Self, Self::Error> {
-        Host::try_from(value.as_bytes())
+impl<'password> PartialEq<Password<'password>> for [u8] {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        percent_encoded_equality(self, other.password.as_bytes(), true)
     }
 }
 
-/// The password component of the authority as defined in
-/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
-///
-/// Even though this library supports parsing the password from the user information, it should be
-/// noted that the format "username:password" is deprecated. Also, be careful logging this!
+impl<'a> PartialEq<&'a [u8]> for Password<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other, true)
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for &[u8] {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        percent_encoded_equality(self, other.password.as_bytes(), true)
+    }
+}
+
+impl PartialEq<str> for Password<'_> {
+    fn eq(&self, other: &str) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other.as_bytes(), true)
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for str {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.password.as_bytes(), true)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Password<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        percent_encoded_equality(self.password.as_bytes(), other.as_bytes(), true)
+    }
+}
+
+impl<'password> PartialEq<Password<'password>> for &str {
+    fn eq(&self, other: &Password<'password>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.password.as_bytes(), true)
+    }
+}
+
+impl PartialOrd for Password<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'password> TryFrom<&'password [u8]> for Password<'password> {
+    type Error = PasswordError;
+
+    fn try_from(value: &'password [u8]) -> Result<Self, Self::Error> {
+        let normalized = match check_user_info(value, false) {
+            Ok(normalized) => normalized,
+            Err(UserInfoError::Password(error)) => return Err(error),
+            Err(UserInfoError::Username(_)) => unreachable!("password checked as username"),
+        };
+
+        // Unsafe: The function above [`check_user_info`] ensures this is valid ASCII-US.
+        Ok(Password {
+            normalized,
+            password: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        })
+    }
+}
+
+impl<'password> TryFrom<&'password str> for Password<'password> {
+    type Error = PasswordError;
+
+    fn try_from(value: &'password str) -> Result<Self, Self::Error> {
+        Password::try_from(value.as_bytes())
+    }
+}
+
+/// A host that is a registered name (i.e. not an IP literal).
 ///
-/// The password is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
-/// for characters in the unreserved character set meaning that `"password"` and `"p%61ssword"` are
-/// identical. Both of these attributes are reflected in the equality and hash functions.
+/// The registered name is case-insensitive meaning that `"example.com"` and `"ExAmPlE.CoM"` refer
+/// to the same registered name. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"example.com"` and
+/// `"ex%61mple.com"` are identical. Both of these attributes are reflected in the equality and hash
+/// functions.
 ///
-/// Be aware that just because percent-encoding plays no role in equality checking does not
-/// mean that the password is normalized. If the password needs to be normalized, use the
-/// [`Password::normalize`] function.
+/// However, be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the host is normalized. If the registered name needs to be normalized, use the
+/// [`RegisteredName::normalize`] function.
 #[derive(Clone, Debug)]
-pub struct Password<'password> {
-    /// Whether the password is normalized.
+pub struct RegisteredName<'name> {
+    /// Whether the registered name is normalized.
     normalized: bool,
 
-    /// The internal password source that is either owned or borrowed.
-    password: Cow<'password, str>,
+    /// The internal registered name source that is either owned or borrowed.
+    registered_name: Cow<'name, str>,
 }
 
-impl Password<'_> {
-    /// Returns a new password which is identical but has a lifetime tied to this password.
-    pub fn as_borrowed(&self) -> Password {
+impl RegisteredName<'_> {
+    /// Returns a new registered name which is identical but has a lifetime tied to this registered
+    /// name.
+    pub fn as_borrowed(&self) -> RegisteredName<'_> {
         use self::Cow::*;
 
-        let password = match &self.password {
+        let name = match &self.registered_name {
             Borrowed(borrowed) => *borrowed,
             Owned(owned) => owned.as_str(),
         };
 
-        Password {
+        RegisteredName {
             normalized: self.normalized,
-            password: Cow::Borrowed(password),
+            registered_name: Cow::Borrowed(name),
         }
     }
 
-    /// Returns a `str` representation of the password.
+    /// Returns a `str` representation of the registered name.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Password;
+    /// use uriparse::RegisteredName;
     ///
-    /// let password = Password::try_from("password").unwrap();
-    /// assert_eq!(password, "password");
+    /// let name = RegisteredName::try_from("example.com").unwrap();
+    /// assert_eq!(name.as_str(), "example.com");
     /// ```
     pub fn as_str(&self) -> &str {
-        &self.password
+        &self.registered_name
     }
 
-    /// Converts the [`Password`] into an owned copy.
+    /// Converts the [`RegisteredName`] into an owned copy.
     ///
-    /// If you construct the authority from a source with a non-static lifetime, you may run into
-    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
-    /// that the returned value has a static lifetime.
+    /// If you construct the registered name from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
     ///
-    /// This is different from just cloning. Cloning the password will just copy the references, and
-    /// thus the lifetime will remain the same.
-    pub fn into_owned(self) -> Password<'static> {
-        Password {
+    /// This is different from just cloning. Cloning the registered name will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> RegisteredName<'static> {
+        RegisteredName {
             normalized: self.normalized,
-            password: Cow::from(self.password.into_owned()),
+            registered_name: Cow::from(self.registered_name.into_owned()),
         }
     }
 
-    /// Returns whether the password is normalized.
+    /// Returns whether the registered name is normalized.
     ///
-    /// A normalized password will have no bytes that are in the unreserved character set
-    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    /// Registered names are considered normalized if all characters are lowercase, no bytes that
+    /// are in the unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
     ///
     /// This function runs in constant-time.
     ///
@@ -1132,244 +2337,483 @@ impl Password<'_> {
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Password;
+    /// use uriparse::RegisteredName;
     ///
-    /// let password = Password::try_from("password").unwrap();
-    /// assert!(password.is_normalized());
+    /// let name = RegisteredName::try_from("example.com").unwrap();
+    /// assert!(name.is_normalized());
     ///
-    /// let mut password = Password::try_from("%ff%ff").unwrap();
-    /// assert!(!password.is_normalized());
-    /// password.normalize();
-    /// assert!(password.is_normalized());
+    /// let mut name = RegisteredName::try_from("EXAMPLE.COM").unwrap();
+    /// assert!(!name.is_normalized());
+    /// name.normalize();
+    /// assert!(name.is_normalized());
     /// ```
     pub fn is_normalized(&self) -> bool {
         self.normalized
     }
 
-    /// Normalizes the password such that it will have no bytes that are in the unreserved character
-    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
+    /// Normalizes the registered name such that all characters are lowercase, no bytes that are in
+    /// the unreserved character set are percent-encoded, and all alphabetical characters in
+    /// percent-encodings are uppercase.
     ///
-    /// If the password is already normalized, the function will return immediately. Otherwise, if
-    /// the password is not owned, this function will perform an allocation to clone it. The
-    /// normalization itself though, is done in-place with no extra memory allocations required.
+    /// If the registered name is already normalized, the function will return immediately.
+    /// Otherwise, if the registered name is not owned, this function will perform an allocation to
+    /// clone it. The normalization itself though, is done in-place with no extra memory allocations
+    /// required.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Password;
+    /// use uriparse::RegisteredName;
     ///
-    /// let mut password = Password::try_from("password").unwrap();
-    /// password.normalize();
-    /// assert_eq!(password, "password");
+    /// let mut name = RegisteredName::try_from("example.com").unwrap();
+    /// name.normalize();
+    /// assert_eq!(name.to_string(), "example.com");
     ///
-    /// let mut password = Password::try_from("%ff%41").unwrap();
-    /// assert_eq!(password, "%ff%41");
-    /// password.normalize();
-    /// assert_eq!(password, "%FFA");
+    /// let mut name = RegisteredName::try_from("%ff%41").unwrap();
+    /// assert_eq!(name.to_string(), "%ff%41");
+    /// name.normalize();
+    /// assert_eq!(name.to_string(), "%FFa");
     /// ```
     pub fn normalize(&mut self) {
         if !self.normalized {
-            // Unsafe: Passwords must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.password.to_mut(), true) };
+            // Unsafe: Registered names must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.registered_name.to_mut(), false) };
             self.normalized = true;
         }
     }
 }
 
-impl AsRef<[u8]> for Password<'_> {
+impl AsRef<[u8]> for RegisteredName<'_> {
     fn as_ref(&self) -> &[u8] {
-        self.password.as_bytes()
+        self.registered_name.as_bytes()
     }
 }
 
-impl AsRef<str> for Password<'_> {
+impl AsRef<str> for RegisteredName<'_> {
     fn as_ref(&self) -> &str {
-        &self.password
-    }
-}
-
-impl Deref for Password<'_> {
-    type Target = str;
-
-    fn deref(&self) -> &Self::Target {
-        &self.password
+        &self.registered_name
     }
 }
 
-impl Display for Password<'_> {
+impl Display for RegisteredName<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        formatter.write_str(&self.password)
+        formatter.write_str(&self.registered_name)
     }
 }
 
-impl Eq for Password<'_> {}
+impl Eq for RegisteredName<'_> {}
 
-impl<'password> From<Password<'password>> for String {
-    fn from(value: Password<'password>) -> String {
+impl<'name> From<RegisteredName<'name>> for String {
+    fn from(value: RegisteredName<'name>) -> String {
         value.to_string()
     }
 }
 
-impl Hash for Password<'_> {
+impl Hash for RegisteredName<'_> {
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
-        percent_encoded_hash(self.password.as_bytes(), state, true);
+        percent_encoded_hash(self.registered_name.as_bytes(), state, false);
     }
 }
 
-impl PartialEq for Password<'_> {
-    fn eq(&self, other: &Password) -> bool {
-        percent_encoded_equality(self.password.as_bytes(), other.password.as_bytes(), true)
+impl Ord for RegisteredName<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(
+            self.registered_name.as_bytes(),
+            other.registered_name.as_bytes(),
+            false,
+        )
     }
 }
 
-impl PartialEq<[u8]> for Password<'_> {
+impl PartialEq for RegisteredName<'_> {
+    fn eq(&self, other: &RegisteredName) -> bool {
+        percent_encoded_equality(
+            self.registered_name.as_bytes(),
+            other.registered_name.as_bytes(),
+            false,
+        )
+    }
+}
+
+impl PartialEq<[u8]> for RegisteredName<'_> {
     fn eq(&self, other: &[u8]) -> bool {
-        percent_encoded_equality(self.password.as_bytes(), other, true)
+        percent_encoded_equality(self.registered_name.as_bytes(), other, false)
     }
 }
 
-impl<'password> PartialEq<Password<'password>> for [u8] {
-    fn eq(&self, other: &Password<'password>) -> bool {
-        percent_encoded_equality(self, other.password.as_bytes(), true)
+impl<'name> PartialEq<RegisteredName<'name>> for [u8] {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        percent_encoded_equality(self, other.registered_name.as_bytes(), false)
     }
 }
 
-impl<'a> PartialEq<&'a [u8]> for Password<'_> {
+impl<'a> PartialEq<&'a [u8]> for RegisteredName<'_> {
     fn eq(&self, other: &&'a [u8]) -> bool {
-        percent_encoded_equality(self.password.as_bytes(), other, true)
+        percent_encoded_equality(self.registered_name.as_bytes(), other, false)
     }
 }
 
-impl<'a, 'password> PartialEq<Password<'password>> for &'a [u8] {
-    fn eq(&self, other: &Password<'password>) -> bool {
-        percent_encoded_equality(self, other.password.as_bytes(), true)
+impl<'name> PartialEq<RegisteredName<'name>> for &[u8] {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        percent_encoded_equality(self, other.registered_name.as_bytes(), false)
     }
 }
 
-impl PartialEq<str> for Password<'_> {
+impl PartialEq<str> for RegisteredName<'_> {
     fn eq(&self, other: &str) -> bool {
-        percent_encoded_equality(self.password.as_bytes(), other.as_bytes(), true)
+        percent_encoded_equality(self.registered_name.as_bytes(), other.as_bytes(), false)
     }
 }
 
-impl<'password> PartialEq<Password<'password>> for str {
-    fn eq(&self, other: &Password<'password>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.password.as_bytes(), true)
+impl<'name> PartialEq<RegisteredName<'name>> for str {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.registered_name.as_bytes(), false)
     }
 }
 
-impl<'a> PartialEq<&'a str> for Password<'_> {
+impl<'a> PartialEq<&'a str> for RegisteredName<'_> {
     fn eq(&self, other: &&'a str) -> bool {
-        percent_encoded_equality(self.password.as_bytes(), other.as_bytes(), true)
+        percent_encoded_equality(self.registered_name.as_bytes(), other.as_bytes(), false)
+    }
+}
+
+impl<'name> PartialEq<RegisteredName<'name>> for &str {
+    fn eq(&self, other: &RegisteredName<'name>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.registered_name.as_bytes(), false)
+    }
+}
+
+impl PartialOrd for RegisteredName<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'name> TryFrom<&'name [u8]> for RegisteredName<'name> {
+    type Error = RegisteredNameError;
+
+    fn try_from(value: &'name [u8]) -> Result<Self, Self::Error> {
+        match Host::try_from(value) {
+            Ok(Host::RegisteredName(name)) => Ok(name),
+            _ => Err(RegisteredNameError),
+        }
+    }
+}
+
+impl<'name> TryFrom<&'name str> for RegisteredName<'name> {
+    type Error = RegisteredNameError;
+
+    fn try_from(value: &'name str) -> Result<Self, Self::Error> {
+        RegisteredName::try_from(value.as_bytes())
+    }
+}
+
+/// The user information component of the authority as defined in
+/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
+///
+/// The user information consists of a username and an optional password, separated by a `':'`. It
+/// does not include the `'@'` that separates it from the host.
+///
+/// Equality and hashing are the same as for the individual [`Username`] and [`Password`]
+/// components.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct UserInfo<'user_info> {
+    /// The password, if the user information contains a `':'`.
+    password: Option<Password<'user_info>>,
+
+    /// The username.
+    username: Username<'user_info>,
+}
+
+impl<'user_info> UserInfo<'user_info> {
+    /// Returns a new user information which is identical but has a lifetime tied to this user
+    /// information.
+    pub fn as_borrowed(&self) -> UserInfo<'_> {
+        UserInfo {
+            password: self.password.as_ref().map(Password::as_borrowed),
+            username: self.username.as_borrowed(),
+        }
+    }
+
+    /// Compares the user information with another in constant time.
+    ///
+    /// Both the usernames and the passwords are compared as described in [`Password::ct_eq`].
+    /// Whether either side has a password is not hidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::UserInfo;
+    ///
+    /// let user_info = UserInfo::try_from("user:pass").unwrap();
+    /// assert!(user_info.ct_eq(&UserInfo::try_from("user:pass").unwrap()));
+    /// assert!(!user_info.ct_eq(&UserInfo::try_from("user:password").unwrap()));
+    /// assert!(!user_info.ct_eq(&UserInfo::try_from("user").unwrap()));
+    /// ```
+    pub fn ct_eq(&self, other: &UserInfo) -> bool {
+        let username = percent_decoded_constant_time_equality(
+            self.username.as_str().as_bytes(),
+            other.username.as_str().as_bytes(),
+        );
+        let password = match (&self.password, &other.password) {
+            (Some(password), Some(other_password)) => password.ct_eq(other_password),
+            (None, None) => true,
+            _ => false,
+        };
+
+        username & password
+    }
+
+    /// Returns whether there is a password in the user information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::UserInfo;
+    ///
+    /// assert!(UserInfo::try_from("username:password").unwrap().has_password());
+    /// assert!(UserInfo::try_from("username:").unwrap().has_password());
+    /// assert!(!UserInfo::try_from("username").unwrap().has_password());
+    /// ```
+    pub fn has_password(&self) -> bool {
+        self.password.is_some()
+    }
+
+    /// Converts the [`UserInfo`] into an owned copy.
+    ///
+    /// If you construct the user information from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the user information will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> UserInfo<'static> {
+        UserInfo {
+            password: self.password.map(Password::into_owned),
+            username: self.username.into_owned(),
+        }
+    }
+
+    /// Consumes the user information and returns its parts: username and password.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::UserInfo;
+    ///
+    /// let user_info = UserInfo::try_from("username:password").unwrap();
+    /// let (username, password) = user_info.into_parts();
+    /// assert_eq!(username, "username");
+    /// assert_eq!(password.unwrap(), "password");
+    /// ```
+    pub fn into_parts(self) -> (Username<'user_info>, Option<Password<'user_info>>) {
+        (self.username, self.password)
+    }
+
+    /// Constructs a new [`UserInfo`] from a username and an optional password.
+    ///
+    /// The lifetime used by the resulting value will be the lifetime of the part that is most
+    /// restricted in scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::UserInfo;
+    ///
+    /// let user_info = UserInfo::new("username", Some("password")).unwrap();
+    /// assert_eq!(user_info.to_string(), "username:password");
+    ///
+    /// let user_info = UserInfo::new("username", None::<&str>).unwrap();
+    /// assert_eq!(user_info.to_string(), "username");
+    /// ```
+    pub fn new<'new_user_info, TUsername, TPassword, TUsernameError, TPasswordError>(
+        username: TUsername,
+        password: Option<TPassword>,
+    ) -> Result<UserInfo<'new_user_info>, UserInfoError>
+    where
+        Username<'new_user_info>: TryFrom<TUsername, Error = TUsernameError>,
+        Password<'new_user_info>: TryFrom<TPassword, Error = TPasswordError>,
+        UserInfoError: From<TUsernameError> + From<TPasswordError>,
+    {
+        let username = Username::try_from(username)?;
+        let password = match password {
+            Some(password) => Some(Password::try_from(password)?),
+            None => None,
+        };
+
+        Ok(UserInfo { password, username })
+    }
+
+    /// The password of the user information, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::UserInfo;
+    ///
+    /// let user_info = UserInfo::try_from("username:password").unwrap();
+    /// assert_eq!(user_info.password().unwrap(), "password");
+    /// ```
+    pub fn password(&self) -> Option<&Password<'user_info>> {
+        self.password.as_ref()
+    }
+
+    /// The username of the user information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::UserInfo;
+    ///
+    /// let user_info = UserInfo::try_from("username:password").unwrap();
+    /// assert_eq!(user_info.username(), "username");
+    /// ```
+    pub fn username(&self) -> &Username<'user_info> {
+        &self.username
+    }
+}
+
+impl Display for UserInfo<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        self.username.fmt(formatter)?;
+
+        if let Some(ref password) = self.password {
+            formatter.write_char(':')?;
+            password.fmt(formatter)?;
+        }
+
+        Ok(())
     }
 }
 
-impl<'a, 'password> PartialEq<Password<'password>> for &'a str {
-    fn eq(&self, other: &Password<'password>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.password.as_bytes(), true)
+impl<'user_info> From<UserInfo<'user_info>> for String {
+    fn from(value: UserInfo<'user_info>) -> String {
+        value.to_string()
     }
 }
 
-impl<'password> TryFrom<&'password [u8]> for Password<'password> {
-    type Error = PasswordError;
-
-    fn try_from(value: &'password [u8]) -> Result<Self, Self::Error> {
-        let normalized = check_user_info(value, false)?;
+impl<'user_info> TryFrom<&'user_info [u8]> for UserInfo<'user_info> {
+    type Error = UserInfoError;
 
-        // Unsafe: The function above [`check_user_info`] ensures this is valid ASCII-US.
-        Ok(Password {
-            normalized,
-            password: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
-        })
+    fn try_from(value: &'user_info [u8]) -> Result<Self, Self::Error> {
+        let (username, password) = parse_user_info(value).map_err(|(error, _)| error)?;
+        Ok(UserInfo { password, username })
     }
 }
 
-impl<'password> TryFrom<&'password str> for Password<'password> {
-    type Error = PasswordError;
+impl<'user_info> TryFrom<&'user_info str> for UserInfo<'user_info> {
+    type Error = UserInfoError;
 
-    fn try_from(value: &'password str) -> Result<Self, Self::Error> {
-        Password::try_from(value.as_bytes())
+    fn try_from(value: &'user_info str) -> Result<Self, Self::Error> {
+        UserInfo::try_from(value.as_bytes())
     }
 }
 
-/// A host that is a registered name (i.e. not an IP literal).
+/// The username component of the authority as defined in
+/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
 ///
-/// The registered name is case-insensitive meaning that `"example.com"` and `"ExAmPlE.CoM"` refer
-/// to the same registered name. Furthermore, percent-encoding plays no role in equality checking
-/// for characters in the unreserved character set meaning that `"example.com"` and
-/// `"ex%61mple.com"` are identical. Both of these attributes are reflected in the equality and hash
-/// functions.
+/// The username is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
+/// for characters in the unreserved character set meaning that `"username"` and `"usern%61me"` are
+/// identical. Both of these attributes are reflected in the equality and hash functions.
 ///
-/// However, be aware that just because percent-encoding plays no role in equality checking does not
-/// mean that the host is normalized. If the registered name needs to be normalized, use the
-/// [`RegisteredName::normalize`] function.
+/// Be aware that just because percent-encoding plays no role in equality checking does not
+/// mean that the username is normalized. If the username needs to be normalized, use the
+/// [`Username::normalize`] function.
 #[derive(Clone, Debug)]
-pub struct RegisteredName<'name> {
-    /// Whether the registered name is normalized.
+pub struct Username<'username> {
+    /// Whether the username is normalized.
     normalized: bool,
 
-    /// The internal registered name source that is either owned or borrowed.
-    registered_name: Cow<'name, str>,
+    /// The internal username source that is either owned or borrowed.
+    username: Cow<'username, str>,
 }
 
-impl RegisteredName<'_> {
-    /// Returns a new registered name which is identical but has a lifetime tied to this registered
-    /// name.
-    pub fn as_borrowed(&self) -> RegisteredName {
+impl Username<'_> {
+    /// Returns a new username which is identical but has a lifetime tied to this username.
+    pub fn as_borrowed(&self) -> Username<'_> {
         use self::Cow::*;
 
-        let name = match &self.registered_name {
+        let username = match &self.username {
             Borrowed(borrowed) => *borrowed,
             Owned(owned) => owned.as_str(),
         };
 
-        RegisteredName {
+        Username {
             normalized: self.normalized,
-            registered_name: Cow::Borrowed(name),
+            username: Cow::Borrowed(username),
         }
     }
 
-    /// Returns a `str` representation of the registered name.
+    /// Returns a `str` representation of the username.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::RegisteredName;
+    /// use uriparse::Username;
     ///
-    /// let name = RegisteredName::try_from("example.com").unwrap();
-    /// assert_eq!(name.as_str(), "example.com");
+    /// let username = Username::try_from("username").unwrap();
+    /// assert_eq!(username.as_str(), "username");
     /// ```
     pub fn as_str(&self) -> &str {
-        &self.registered_name
+        &self.username
     }
 
-    /// Converts the [`RegisteredName`] into an owned copy.
+    /// Returns the username with all percent-encoded bytes decoded.
     ///
-    /// If you construct the registered name from a source with a non-static lifetime, you may run
-    /// into lifetime problems due to the way the struct is designed. Calling this function will
-    /// ensure that the returned value has a static lifetime.
+    /// If the username contains no percent-encodings, the returned value will borrow from the
+    /// username. Decoded byte sequences that are not valid UTF-8 are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
     ///
-    /// This is different from just cloning. Cloning the registered name will just copy the
-    /// references, and thus the lifetime will remain the same.
-    pub fn into_owned(self) -> RegisteredName<'static> {
-        RegisteredName {
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Username;
+    ///
+    /// let username = Username::try_from("user%40example.com").unwrap();
+    /// assert_eq!(username.decode(), "user@example.com");
+    /// ```
+    pub fn decode(&self) -> Cow<'_, str> {
+        decode_percent_encoded(&self.username)
+    }
+
+    /// Converts the [`Username`] into an owned copy.
+    ///
+    /// If you construct the username from a source with a non-static lifetime, you may run into
+    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
+    /// that the returned value has a static lifetime.
+    ///
+    /// This is different from just cloning. Cloning the username will just copy the references, and
+    /// thus the lifetime will remain the same.
+    pub fn into_owned(self) -> Username<'static> {
+        Username {
             normalized: self.normalized,
-            registered_name: Cow::from(self.registered_name.into_owned()),
+            username: Cow::from(self.username.into_owned()),
         }
     }
 
-    /// Returns whether the registered name is normalized.
+    /// Returns whether the username is normalized.
     ///
-    /// Registered names are considered normalized if all characters are lowercase, no bytes that
-    /// are in the unreserved character set are percent-encoded, and all alphabetical characters in
-    /// percent-encodings are uppercase.
+    /// A normalized username will have no bytes that are in the unreserved character set
+    /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
     /// This function runs in constant-time.
     ///
@@ -1378,235 +2822,256 @@ impl RegisteredName<'_> {
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::RegisteredName;
+    /// use uriparse::Username;
     ///
-    /// let name = RegisteredName::try_from("example.com").unwrap();
-    /// assert!(name.is_normalized());
+    /// let username = Username::try_from("username").unwrap();
+    /// assert!(username.is_normalized());
     ///
-    /// let mut name = RegisteredName::try_from("EXAMPLE.COM").unwrap();
-    /// assert!(!name.is_normalized());
-    /// name.normalize();
-    /// assert!(name.is_normalized());
+    /// let mut username = Username::try_from("%ff%ff").unwrap();
+    /// assert!(!username.is_normalized());
+    /// username.normalize();
+    /// assert!(username.is_normalized());
     /// ```
     pub fn is_normalized(&self) -> bool {
         self.normalized
     }
 
-    /// Normalizes the registered name such that all characters are lowercase, no bytes that are in
-    /// the unreserved character set are percent-encoded, and all alphabetical characters in
-    /// percent-encodings are uppercase.
+    /// Normalizes the username such that it will have no bytes that are in the unreserved character
+    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
-    /// If the registered name is already normalized, the function will return immediately.
-    /// Otherwise, if the registered name is not owned, this function will perform an allocation to
-    /// clone it. The normalization itself though, is done in-place with no extra memory allocations
-    /// required.
+    /// If the username is already normalized, the function will return immediately. Otherwise, if
+    /// the username is not owned, this function will perform an allocation to clone it. The
+    /// normalization itself though, is done in-place with no extra memory allocations required.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::RegisteredName;
+    /// use uriparse::Username;
     ///
-    /// let mut name = RegisteredName::try_from("example.com").unwrap();
-    /// name.normalize();
-    /// assert_eq!(name.to_string(), "example.com");
+    /// let mut username = Username::try_from("username").unwrap();
+    /// username.normalize();
+    /// assert_eq!(username, "username");
     ///
-    /// let mut name = RegisteredName::try_from("%ff%41").unwrap();
-    /// assert_eq!(name.to_string(), "%ff%41");
-    /// name.normalize();
-    /// assert_eq!(name.to_string(), "%FFA");
+    /// let mut username = Username::try_from("%ff%41").unwrap();
+    /// assert_eq!(username, "%ff%41");
+    /// username.normalize();
+    /// assert_eq!(username, "%FFA");
     /// ```
     pub fn normalize(&mut self) {
         if !self.normalized {
-            // Unsafe: Registered names must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.registered_name.to_mut(), false) };
+            // Unsafe: Usernames must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.username.to_mut(), true) };
             self.normalized = true;
         }
     }
 }
 
-impl AsRef<[u8]> for RegisteredName<'_> {
+impl AsRef<[u8]> for Username<'_> {
     fn as_ref(&self) -> &[u8] {
-        self.registered_name.as_bytes()
+        self.username.as_bytes()
     }
 }
 
-impl AsRef<str> for RegisteredName<'_> {
+impl AsRef<str> for Username<'_> {
     fn as_ref(&self) -> &str {
-        &self.registered_name
+        &self.username
     }
 }
 
-impl Display for RegisteredName<'_> {
+impl Deref for Username<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.username
+    }
+}
+
+impl Display for Username<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        formatter.write_str(&self.registered_name)
+        formatter.write_str(&self.username)
     }
 }
 
-impl Eq for RegisteredName<'_> {}
+impl<'username> Eq for Username<'username> {}
 
-impl<'name> From<RegisteredName<'name>> for String {
-    fn from(value: RegisteredName<'name>) -> String {
+impl<'username> From<Username<'username>> for String {
+    fn from(value: Username<'username>) -> String {
         value.to_string()
     }
 }
 
-impl Hash for RegisteredName<'_> {
+impl Hash for Username<'_> {
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
-        percent_encoded_hash(self.registered_name.as_bytes(), state, false);
+        percent_encoded_hash(self.username.as_bytes(), state, true);
     }
 }
 
-impl PartialEq for RegisteredName<'_> {
-    fn eq(&self, other: &RegisteredName) -> bool {
-        percent_encoded_equality(
-            self.registered_name.as_bytes(),
-            other.registered_name.as_bytes(),
-            false,
-        )
+impl Ord for Username<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.username.as_bytes(), other.username.as_bytes(), true)
     }
 }
 
-impl PartialEq<[u8]> for RegisteredName<'_> {
+impl PartialEq for Username<'_> {
+    fn eq(&self, other: &Username) -> bool {
+        percent_encoded_equality(self.username.as_bytes(), other.username.as_bytes(), true)
+    }
+}
+
+impl PartialEq<[u8]> for Username<'_> {
     fn eq(&self, other: &[u8]) -> bool {
-        percent_encoded_equality(self.registered_name.as_bytes(), other, false)
+        percent_encoded_equality(self.username.as_bytes(), other, true)
     }
 }
 
-impl<'name> PartialEq<RegisteredName<'name>> for [u8] {
-    fn eq(&self, other: &RegisteredName<'name>) -> bool {
-        percent_encoded_equality(self, other.registered_name.as_bytes(), false)
+impl<'username> PartialEq<Username<'username>> for [u8] {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        percent_encoded_equality(self, other.as_bytes(), true)
     }
 }
 
-impl<'a> PartialEq<&'a [u8]> for RegisteredName<'_> {
+impl<'a> PartialEq<&'a [u8]> for Username<'_> {
     fn eq(&self, other: &&'a [u8]) -> bool {
-        percent_encoded_equality(self.registered_name.as_bytes(), other, false)
+        percent_encoded_equality(self.username.as_bytes(), other, true)
     }
 }
 
-impl<'a, 'name> PartialEq<RegisteredName<'name>> for &'a [u8] {
-    fn eq(&self, other: &RegisteredName<'name>) -> bool {
-        percent_encoded_equality(self, other.registered_name.as_bytes(), false)
+impl<'username> PartialEq<Username<'username>> for &[u8] {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        percent_encoded_equality(self, other.as_bytes(), true)
     }
 }
 
-impl PartialEq<str> for RegisteredName<'_> {
+impl PartialEq<str> for Username<'_> {
     fn eq(&self, other: &str) -> bool {
-        percent_encoded_equality(self.registered_name.as_bytes(), other.as_bytes(), false)
+        percent_encoded_equality(self.username.as_bytes(), other.as_bytes(), true)
     }
 }
 
-impl<'name> PartialEq<RegisteredName<'name>> for str {
-    fn eq(&self, other: &RegisteredName<'name>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.registered_name.as_bytes(), false)
+impl<'username> PartialEq<Username<'username>> for str {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.username.as_bytes(), true)
     }
 }
 
-impl<'a> PartialEq<&'a str> for RegisteredName<'_> {
+impl<'a> PartialEq<&'a str> for Username<'_> {
     fn eq(&self, other: &&'a str) -> bool {
-        percent_encoded_equality(self.registered_name.as_bytes(), other.as_bytes(), false)
+        percent_encoded_equality(self.username.as_bytes(), other.as_bytes(), true)
     }
 }
 
-impl<'a, 'name> PartialEq<RegisteredName<'name>> for &'a str {
-    fn eq(&self, other: &RegisteredName<'name>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.registered_name.as_bytes(), false)
+impl<'username> PartialEq<Username<'username>> for &str {
+    fn eq(&self, other: &Username<'username>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.username.as_bytes(), true)
     }
 }
 
-impl<'name> TryFrom<&'name [u8]> for RegisteredName<'name> {
-    type Error = RegisteredNameError;
+impl PartialOrd for Username<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    fn try_from(value: &'name [u8]) -> Result<Self, Self::Error> {
-        match Host::try_from(value) {
-            Ok(Host::RegisteredName(name)) => Ok(name),
-            _ => Err(RegisteredNameError),
-        }
+impl<'username> TryFrom<&'username [u8]> for Username<'username> {
+    type Error = UsernameError;
+
+    fn try_from(value: &'username [u8]) -> Result<Self, Self::Error> {
+        let normalized = match check_user_info(value, true) {
+            Ok(normalized) => normalized,
+            Err(UserInfoError::Username(error)) => return Err(error),
+            Err(UserInfoError::Password(_)) => unreachable!("username checked as password"),
+        };
+
+        // Unsafe: The function above [`check_user_info`] ensure this is valid ASCII-US.
+        Ok(Username {
+            normalized,
+            username: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        })
     }
 }
 
-impl<'name> TryFrom<&'name str> for RegisteredName<'name> {
-    type Error = RegisteredNameError;
+impl<'username> TryFrom<&'username str> for Username<'username> {
+    type Error = UsernameError;
 
-    fn try_from(value: &'name str) -> Result<Self, Self::Error> {
-        RegisteredName::try_from(value.as_bytes())
+    fn try_from(value: &'username str) -> Result<Self, Self::Error> {
+        Username::try_from(value.as_bytes())
     }
 }
 
-/// The username component of the authority as defined in
-/// [[RFC3986, Section 3.2.1](https://tools.ietf.org/html/rfc3986#section-3.2.1)].
-///
-/// The username is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
-/// for characters in the unreserved character set meaning that `"username"` and `"usern%61me"` are
+/// The zone identifier of an IPv6 address as defined in
+/// [[RFC6874](https://tools.ietf.org/html/rfc6874)].
+///
+/// The zone identifier is case-sensitive. Furthermore, percent-encoding plays no role in equality
+/// checking for characters in the unreserved character set meaning that `"eth0"` and `"%65th0"` are
 /// identical. Both of these attributes are reflected in the equality and hash functions.
 ///
-/// Be aware that just because percent-encoding plays no role in equality checking does not
-/// mean that the username is normalized. If the username needs to be normalized, use the
-/// [`Username::normalize`] function.
+/// Note that the zone identifier does not include the `"%25"` that separates it from the IPv6
+/// address.
 #[derive(Clone, Debug)]
-pub struct Username<'username> {
-    /// Whether the username is normalized.
+pub struct ZoneIdentifier<'zone> {
+    /// Whether the zone identifier is normalized.
     normalized: bool,
 
-    /// The internal username source that is either owned or borrowed.
-    username: Cow<'username, str>,
+    /// The internal zone identifier source that is either owned or borrowed.
+    zone_identifier: Cow<'zone, str>,
 }
 
-impl Username<'_> {
-    /// Returns a new username which is identical but has a lifetime tied to this username.
-    pub fn as_borrowed(&self) -> Username {
+impl ZoneIdentifier<'_> {
+    /// Returns a new zone identifier which is identical but has a lifetime tied to this zone
+    /// identifier.
+    pub fn as_borrowed(&self) -> ZoneIdentifier<'_> {
         use self::Cow::*;
 
-        let username = match &self.username {
+        let zone_identifier = match &self.zone_identifier {
             Borrowed(borrowed) => *borrowed,
             Owned(owned) => owned.as_str(),
         };
 
-        Username {
+        ZoneIdentifier {
             normalized: self.normalized,
-            username: Cow::Borrowed(username),
+            zone_identifier: Cow::Borrowed(zone_identifier),
         }
     }
 
-    /// Returns a `str` representation of the username.
+    /// Returns a `str` representation of the zone identifier.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Username;
+    /// use uriparse::ZoneIdentifier;
     ///
-    /// let username = Username::try_from("username").unwrap();
-    /// assert_eq!(username.as_str(), "username");
+    /// let zone_identifier = ZoneIdentifier::try_from("eth0").unwrap();
+    /// assert_eq!(zone_identifier.as_str(), "eth0");
     /// ```
     pub fn as_str(&self) -> &str {
-        &self.username
+        &self.zone_identifier
     }
 
-    /// Converts the [`Username`] into an owned copy.
+    /// Converts the [`ZoneIdentifier`] into an owned copy.
     ///
-    /// If you construct the username from a source with a non-static lifetime, you may run into
-    /// lifetime problems due to the way the struct is designed. Calling this function will ensure
-    /// that the returned value has a static lifetime.
+    /// If you construct the zone identifier from a source with a non-static lifetime, you may run
+    /// into lifetime problems due to the way the struct is designed. Calling this function will
+    /// ensure that the returned value has a static lifetime.
     ///
-    /// This is different from just cloning. Cloning the username will just copy the references, and
-    /// thus the lifetime will remain the same.
-    pub fn into_owned(self) -> Username<'static> {
-        Username {
+    /// This is different from just cloning. Cloning the zone identifier will just copy the
+    /// references, and thus the lifetime will remain the same.
+    pub fn into_owned(self) -> ZoneIdentifier<'static> {
+        ZoneIdentifier {
             normalized: self.normalized,
-            username: Cow::from(self.username.into_owned()),
+            zone_identifier: Cow::from(self.zone_identifier.into_owned()),
         }
     }
 
-    /// Returns whether the username is normalized.
+    /// Returns whether the zone identifier is normalized.
     ///
-    /// A normalized username will have no bytes that are in the unreserved character set
+    /// A normalized zone identifier will have no bytes that are in the unreserved character set
     /// percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
     /// This function runs in constant-time.
@@ -1616,168 +3081,178 @@ impl Username<'_> {
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Username;
+    /// use uriparse::ZoneIdentifier;
     ///
-    /// let username = Username::try_from("username").unwrap();
-    /// assert!(username.is_normalized());
+    /// let zone_identifier = ZoneIdentifier::try_from("eth0").unwrap();
+    /// assert!(zone_identifier.is_normalized());
     ///
-    /// let mut username = Username::try_from("%ff%ff").unwrap();
-    /// assert!(!username.is_normalized());
-    /// username.normalize();
-    /// assert!(username.is_normalized());
+    /// let mut zone_identifier = ZoneIdentifier::try_from("%65th0").unwrap();
+    /// assert!(!zone_identifier.is_normalized());
+    /// zone_identifier.normalize();
+    /// assert!(zone_identifier.is_normalized());
     /// ```
     pub fn is_normalized(&self) -> bool {
         self.normalized
     }
 
-    /// Normalizes the username such that it will have no bytes that are in the unreserved character
-    /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
-    ///
-    /// If the username is already normalized, the function will return immediately. Otherwise, if
-    /// the username is not owned, this function will perform an allocation to clone it. The
-    /// normalization itself though, is done in-place with no extra memory allocations required.
+    /// Normalizes the zone identifier such that it will have no bytes that are in the unreserved
+    /// character set percent-encoded and all alphabetical characters in percent-encodings will be
+    /// uppercase.
     ///
     /// # Examples
     ///
     /// ```
     /// use std::convert::TryFrom;
     ///
-    /// use uriparse::Username;
-    ///
-    /// let mut username = Username::try_from("username").unwrap();
-    /// username.normalize();
-    /// assert_eq!(username, "username");
+    /// use uriparse::ZoneIdentifier;
     ///
-    /// let mut username = Username::try_from("%ff%41").unwrap();
-    /// assert_eq!(username, "%ff%41");
-    /// username.normalize();
-    /// assert_eq!(username, "%FFA");
+    /// let mut zone_identifier = ZoneIdentifier::try_from("%65th0%ff").unwrap();
+    /// zone_identifier.normalize();
+    /// assert_eq!(zone_identifier.as_str(), "eth0%FF");
     /// ```
     pub fn normalize(&mut self) {
         if !self.normalized {
-            // Unsafe: Usernames must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.username.to_mut(), true) };
+            // Unsafe: Zone identifiers must be valid ASCII-US, so this is safe.
+            unsafe { normalize_string(self.zone_identifier.to_mut(), true) };
             self.normalized = true;
         }
     }
 }
 
-impl AsRef<[u8]> for Username<'_> {
+impl AsRef<[u8]> for ZoneIdentifier<'_> {
     fn as_ref(&self) -> &[u8] {
-        self.username.as_bytes()
+        self.zone_identifier.as_bytes()
     }
 }
 
-impl AsRef<str> for Username<'_> {
+impl AsRef<str> for ZoneIdentifier<'_> {
     fn as_ref(&self) -> &str {
-        &self.username
+        &self.zone_identifier
     }
 }
 
-impl Deref for Username<'_> {
+impl Deref for ZoneIdentifier<'_> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        &self.username
+        &self.zone_identifier
     }
 }
 
-impl Display for Username<'_> {
+impl Display for ZoneIdentifier<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        formatter.write_str(&self.username)
+        formatter.write_str(&self.zone_identifier)
     }
 }
 
-impl<'username> Eq for Username<'username> {}
+impl Eq for ZoneIdentifier<'_> {}
 
-impl<'username> From<Username<'username>> for String {
-    fn from(value: Username<'username>) -> String {
+impl<'zone> From<ZoneIdentifier<'zone>> for String {
+    fn from(value: ZoneIdentifier<'zone>) -> String {
         value.to_string()
     }
 }
 
-impl Hash for Username<'_> {
+impl Hash for ZoneIdentifier<'_> {
     fn hash<H>(&self, state: &mut H)
     where
         H: Hasher,
     {
-        percent_encoded_hash(self.username.as_bytes(), state, true);
-    }
-}
-
-impl PartialEq for Username<'_> {
-    fn eq(&self, other: &Username) -> bool {
-        percent_encoded_equality(self.username.as_bytes(), other.username.as_bytes(), true)
+        percent_encoded_hash(self.zone_identifier.as_bytes(), state, true);
     }
 }
 
-impl PartialEq<[u8]> for Username<'_> {
-    fn eq(&self, other: &[u8]) -> bool {
-        percent_encoded_equality(self.username.as_bytes(), other, true)
+impl Ord for ZoneIdentifier<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(
+            self.zone_identifier.as_bytes(),
+            other.zone_identifier.as_bytes(),
+            true,
+        )
     }
 }
 
-impl<'username> PartialEq<Username<'username>> for [u8] {
-    fn eq(&self, other: &Username<'username>) -> bool {
-        percent_encoded_equality(self, other.as_bytes(), true)
+impl PartialEq for ZoneIdentifier<'_> {
+    fn eq(&self, other: &ZoneIdentifier) -> bool {
+        percent_encoded_equality(
+            self.zone_identifier.as_bytes(),
+            other.zone_identifier.as_bytes(),
+            true,
+        )
     }
 }
 
-impl<'a> PartialEq<&'a [u8]> for Username<'_> {
-    fn eq(&self, other: &&'a [u8]) -> bool {
-        percent_encoded_equality(self.username.as_bytes(), other, true)
+impl PartialEq<str> for ZoneIdentifier<'_> {
+    fn eq(&self, other: &str) -> bool {
+        percent_encoded_equality(self.zone_identifier.as_bytes(), other.as_bytes(), true)
     }
 }
 
-impl<'a, 'username> PartialEq<Username<'username>> for &'a [u8] {
-    fn eq(&self, other: &Username<'username>) -> bool {
-        percent_encoded_equality(self, other.as_bytes(), true)
+impl<'zone> PartialEq<ZoneIdentifier<'zone>> for str {
+    fn eq(&self, other: &ZoneIdentifier<'zone>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.zone_identifier.as_bytes(), true)
     }
 }
 
-impl PartialEq<str> for Username<'_> {
-    fn eq(&self, other: &str) -> bool {
-        percent_encoded_equality(self.username.as_bytes(), other.as_bytes(), true)
+impl<'a> PartialEq<&'a str> for ZoneIdentifier<'_> {
+    fn eq(&self, other: &&'a str) -> bool {
+        percent_encoded_equality(self.zone_identifier.as_bytes(), other.as_bytes(), true)
     }
 }
 
-impl<'username> PartialEq<Username<'username>> for str {
-    fn eq(&self, other: &Username<'username>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.username.as_bytes(), true)
+impl<'zone> PartialEq<ZoneIdentifier<'zone>> for &str {
+    fn eq(&self, other: &ZoneIdentifier<'zone>) -> bool {
+        percent_encoded_equality(self.as_bytes(), other.zone_identifier.as_bytes(), true)
     }
 }
 
-impl<'a> PartialEq<&'a str> for Username<'_> {
-    fn eq(&self, other: &&'a str) -> bool {
-        percent_encoded_equality(self.username.as_bytes(), other.as_bytes(), true)
+impl PartialOrd for ZoneIdentifier<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl<'a, 'username> PartialEq<Username<'username>> for &'a str {
-    fn eq(&self, other: &Username<'username>) -> bool {
-        percent_encoded_equality(self.as_bytes(), other.username.as_bytes(), true)
-    }
-}
+impl<'zone> TryFrom<&'zone [u8]> for ZoneIdentifier<'zone> {
+    type Error = ZoneIdentifierError;
 
-impl<'username> TryFrom<&'username [u8]> for Username<'username> {
-    type Error = UsernameError;
+    fn try_from(value: &'zone [u8]) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(ZoneIdentifierError::Empty);
+        }
 
-    fn try_from(value: &'username [u8]) -> Result<Self, Self::Error> {
-        let normalized = check_user_info(value, true)?;
+        let mut bytes = value.iter();
+        let mut normalized = true;
+
+        while let Some(&byte) = bytes.next() {
+            match ZONE_IDENTIFIER_CHAR_MAP[byte as usize] {
+                0 => return Err(ZoneIdentifierError::InvalidCharacter),
+                b'%' => {
+                    match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
+                        Ok((hex_value, uppercase)) => {
+                            if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
+                                normalized = false;
+                            }
+                        }
+                        Err(_) => return Err(ZoneIdentifierError::InvalidPercentEncoding),
+                    }
+                }
+                _ => (),
+            }
+        }
 
-        // Unsafe: The function above [`check_user_info`] ensure this is valid ASCII-US.
-        Ok(Username {
+        // Unsafe: The loop above makes sure the byte string is valid ASCII-US.
+        Ok(ZoneIdentifier {
             normalized,
-            username: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+            zone_identifier: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
         })
     }
 }
 
-impl<'username> TryFrom<&'username str> for Username<'username> {
-    type Error = UsernameError;
+impl<'zone> TryFrom<&'zone str> for ZoneIdentifier<'zone> {
+    type Error = ZoneIdentifierError;
 
-    fn try_from(value: &'username str) -> Result<Self, Self::Error> {
-        Username::try_from(value.as_bytes())
+    fn try_from(value: &'zone str) -> Result<Self, Self::Error> {
+        ZoneIdentifier::try_from(value.as_bytes())
     }
 }
 
@@ -1788,12 +3263,22 @@ pub enum AuthorityError {
     /// The host component of the authority was invalid.
     Host(HostError),
 
+    /// The authority has an empty host, but the policy requires one.
+    ///
+    /// This can only occur when using [`Authority::parse_with_policy`].
+    MissingHost,
+
     /// The password component of the authority was invalid.
     Password(PasswordError),
 
     /// The port component of the authority was invalid.
     Port(PortError),
 
+    /// The authority has user information, but the policy forbids it.
+    ///
+    /// This can only occur when using [`Authority::parse_with_policy`].
+    UserInfoNotAllowed,
+
     /// The username component of the authority was invalid.
     Username(UsernameError),
 }
@@ -1804,13 +3289,16 @@ impl Display for AuthorityError {
 
         match self {
             Host(error) => error.fmt(formatter),
+            MissingHost => write!(formatter, "missing host"),
             Password(error) => error.fmt(formatter),
             Port(error) => error.fmt(formatter),
+            UserInfoNotAllowed => write!(formatter, "user information not allowed"),
             Username(error) => error.fmt(formatter),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for AuthorityError {}
 
 impl From<Infallible> for AuthorityError {
@@ -1858,9 +3346,6 @@ impl From<UsernameError> for AuthorityError {
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
 pub enum HostError {
-    /// The syntax for a future IP literal was used and is not currently supported.
-    AddressMechanismNotSupported,
-
     /// An invalid character for an IPv4 address or registered name was used. Due to the ambiguity
     /// of the grammar, it is not possible to say which. It is also possible that all the characters
     /// were valid, but there was an invalid percent encoding (e.g. `"%ZZ"`).
@@ -1874,9 +3359,18 @@ pub enum HostError {
     /// valid IPv6 characters. However, the format of the literal was invalid.
     InvalidIPv6Format,
 
-    /// The syntax for a future IP literal was used (i.e. `"[v*...]"` where `"*"` is a hexadecimal
-    /// digit), but it contained an invalid character.
+    /// The syntax for a future IP literal was used (i.e. `"[v*.*]"`), but the address contained an
+    /// invalid character.
     InvalidIPvFutureCharacter,
+
+    /// The syntax for a future IP literal was used (i.e. `"[v...]"`), but the format of the literal
+    /// was invalid. The version must be at least one hexadecimal digit and must be followed by a
+    /// `'.'` and a non-empty address.
+    InvalidIPvFutureFormat,
+
+    /// The syntax for an IPv6 literal with a zone identifier was used (i.e. `"[...%25...]"`), but
+    /// the zone identifier was invalid.
+    ZoneIdentifier(ZoneIdentifierError),
 }
 
 impl Display for HostError {
@@ -1884,19 +3378,19 @@ impl Display for HostError {
         use self::HostError::*;
 
         match self {
-            AddressMechanismNotSupported => {
-                write!(formatter, "host address mechanism not supported")
-            }
             InvalidIPv4OrRegisteredNameCharacter => {
                 write!(formatter, "invalid host IPv4 or registered name character")
             }
             InvalidIPv6Character => write!(formatter, "invalid host IPv6 character"),
             InvalidIPv6Format => write!(formatter, "invalid host IPv6 format"),
             InvalidIPvFutureCharacter => write!(formatter, "invalid host IPvFuture character"),
+            InvalidIPvFutureFormat => write!(formatter, "invalid host IPvFuture format"),
+            ZoneIdentifier(error) => error.fmt(formatter),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for HostError {}
 
 impl From<Infallible> for HostError {
@@ -1905,6 +3399,39 @@ impl From<Infallible> for HostError {
     }
 }
 
+impl From<ZoneIdentifierError> for HostError {
+    fn from(value: ZoneIdentifierError) -> Self {
+        HostError::ZoneIdentifier(value)
+    }
+}
+
+/// An error representing a failed IDNA conversion of a host.
+#[cfg(feature = "idna")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IDNAError {
+    /// The registered name is not a valid internationalized domain name.
+    InvalidDomain,
+
+    /// The percent-decoded registered name is not valid UTF-8.
+    InvalidUTF8,
+}
+
+#[cfg(feature = "idna")]
+impl Display for IDNAError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::IDNAError::*;
+
+        match self {
+            InvalidDomain => write!(formatter, "invalid IDNA domain"),
+            InvalidUTF8 => write!(formatter, "invalid IDNA UTF-8"),
+        }
+    }
+}
+
+#[cfg(all(feature = "idna", feature = "std"))]
+impl Error for IDNAError {}
+
 /// An error representing an invalid password component.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -1927,6 +3454,7 @@ impl Display for PasswordError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for PasswordError {}
 
 impl From<Infallible> for PasswordError {
@@ -1935,15 +3463,6 @@ impl From<Infallible> for PasswordError {
     }
 }
 
-impl From<UserInfoError> for PasswordError {
-    fn from(value: UserInfoError) -> Self {
-        match value {
-            UserInfoError::Password(error) => error,
-            _ => panic!("unexpected user info error"),
-        }
-    }
-}
-
 /// An error representing an invalid port.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -1966,6 +3485,7 @@ impl Display for PortError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for PortError {}
 
 impl From<Infallible> for PortError {
@@ -1988,12 +3508,13 @@ impl Display for RegisteredNameError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for RegisteredNameError {}
 
 /// An error representing an invalid user information component.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
-enum UserInfoError {
+pub enum UserInfoError {
     /// The password component of the user information was invalid.
     Password(PasswordError),
 
@@ -2012,6 +3533,7 @@ impl Display for UserInfoError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for UserInfoError {}
 
 impl From<Infallible> for UserInfoError {
@@ -2060,6 +3582,7 @@ impl Display for UsernameError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for UsernameError {}
 
 impl From<Infallible> for UsernameError {
@@ -2068,44 +3591,75 @@ impl From<Infallible> for UsernameError {
     }
 }
 
-impl From<UserInfoError> for UsernameError {
-    fn from(value: UserInfoError) -> Self {
-        match value {
-            UserInfoError::Username(error) => error,
-            _ => panic!("unexpected user info error"),
+/// An error representing an invalid IPv6 zone identifier.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ZoneIdentifierError {
+    /// The zone identifier was empty.
+    Empty,
+
+    /// The zone identifier contained an invalid character.
+    InvalidCharacter,
+
+    /// The zone identifier contained an invalid percent encoding (e.g. `"%ZZ"`).
+    InvalidPercentEncoding,
+}
+
+impl Display for ZoneIdentifierError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::ZoneIdentifierError::*;
+
+        match self {
+            Empty => write!(formatter, "empty zone identifier"),
+            InvalidCharacter => write!(formatter, "invalid zone identifier character"),
+            InvalidPercentEncoding => write!(formatter, "invalid zone identifier percent encoding"),
         }
     }
 }
 
-/// Returns true if the byte string contains only valid IPv4 or registered name characters. This
-/// also ensures that percent encodings are valid.
-fn check_ipv4_or_registered_name(value: &[u8]) -> (bool, bool) {
-    let mut bytes = value.iter();
+#[cfg(feature = "std")]
+impl Error for ZoneIdentifierError {}
+
+impl From<Infallible> for ZoneIdentifierError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+/// Checks that the byte string contains only valid IPv4 or registered name characters and valid
+/// percent encodings. If so, it returns whether the byte string is normalized, otherwise the index
+/// of the first invalid byte.
+fn check_ipv4_or_registered_name(value: &[u8]) -> Result<bool, usize> {
+    let mut bytes = value.iter().enumerate();
     let mut normalized = true;
 
-    while let Some(&byte) = bytes.next() {
+    while let Some((index, &byte)) = bytes.next() {
         match IPV4_AND_REGISTERED_NAME_CHAR_MAP[byte as usize] {
-            0 => return (false, false),
-            b'%' => match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
+            0 => return Err(index),
+            b'%' => match get_percent_encoded_value(
+                bytes.next().map(|(_, &byte)| byte),
+                bytes.next().map(|(_, &byte)| byte),
+            ) {
                 Ok((hex_value, uppercase)) => {
                     if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
                         normalized = false;
                     }
                 }
-                _ => return (false, false),
+                Err(_) => return Err(index),
             },
             b'A'..=b'Z' => normalized = false,
             _ => (),
         }
     }
 
-    (true, normalized)
+    Ok(normalized)
 }
 
-/// Returns true if the byte string contains only valid IPv6 characters.
+/// Returns true if the byte string contains only valid IPv6 characters. This includes `'.'` for
+/// IPv6 addresses with an embedded IPv4 address.
 fn check_ipv6(value: &[u8]) -> bool {
     for &byte in value {
-        if !byte.is_ascii_hexdigit() && byte != b':' {
+        if !byte.is_ascii_hexdigit() && byte != b':' && byte != b'.' {
             return false;
         }
     }
@@ -2113,8 +3667,7 @@ fn check_ipv6(value: &[u8]) -> bool {
     true
 }
 
-/// Returns true if the byte string contains only valid future IP literal characters. This also
-/// ensures that percent encodings are valid.
+/// Returns true if the byte string contains only valid future IP literal characters.
 fn check_ipvfuture(value: &[u8]) -> bool {
     for &byte in value {
         if let 0 = IPV_FUTURE_CHAR_MAP[byte as usize] {
@@ -2163,18 +3716,20 @@ fn check_user_info(value: &[u8], is_username: bool) -> Result<bool, UserInfoErro
 }
 
 /// Parses the authority from the given byte string.
-pub(crate) fn parse_authority(value: &[u8]) -> Result<(Authority, &[u8]), AuthorityError> {
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+pub(crate) fn parse_authority(
+    value: &[u8],
+) -> Result<(Authority<'_>, &[u8]), (AuthorityError, usize)> {
     let mut at_index = None;
     let mut last_colon_index = None;
     let mut end_index = value.len();
 
     for (index, &byte) in value.iter().enumerate() {
         match byte {
-            b'@' => {
-                if at_index.is_none() {
-                    at_index = Some(index);
-                    last_colon_index = None;
-                }
+            b'@' if at_index.is_none() => {
+                at_index = Some(index);
+                last_colon_index = None;
             }
             b':' => last_colon_index = Some(index),
             b']' => last_colon_index = None,
@@ -2189,18 +3744,20 @@ pub(crate) fn parse_authority(value: &[u8]) -> Result<(Authority, &[u8]), Author
     let (value, rest) = value.split_at(end_index);
     let (username, password, host_start_index) = match at_index {
         Some(index) => {
-            let (username, password) = parse_user_info(&value[..index])?;
+            let (username, password) =
+                parse_user_info(&value[..index]).map_err(|(error, index)| (error.into(), index))?;
             (Some(username), password, index + 1)
         }
         None => (None, None, 0),
     };
 
-    let (host, port) = match last_colon_index {
-        Some(index) => (
-            Host::try_from(&value[host_start_index..index])?,
-            parse_port(&value[index + 1..])?,
-        ),
-        None => (Host::try_from(&value[host_start_index..])?, None),
+    let host_end_index = last_colon_index.unwrap_or(value.len());
+    let host = parse_host(&value[host_start_index..host_end_index])
+        .map_err(|(error, index)| (error.into(), host_start_index + index))?;
+    let port = match last_colon_index {
+        Some(index) => parse_port_with_index(&value[index + 1..])
+            .map_err(|(error, port_index)| (error.into(), index + 1 + port_index))?,
+        None => None,
     };
 
     let authority = Authority {
@@ -2213,22 +3770,117 @@ pub(crate) fn parse_authority(value: &[u8]) -> Result<(Authority, &[u8]), Author
     Ok((authority, rest))
 }
 
+/// Parses the host from the given byte string.
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+/// Errors in IP literals are reported at the start of the host.
+fn parse_host(value: &[u8]) -> Result<Host<'_>, (HostError, usize)> {
+    if let (Some(b'['), Some(b']')) = (value.first(), value.last()) {
+        return parse_ip_literal(value).map_err(|error| (error, 0));
+    }
+
+    let normalized = check_ipv4_or_registered_name(value)
+        .map_err(|index| (HostError::InvalidIPv4OrRegisteredNameCharacter, index))?;
+
+    // Unsafe: The function above [`check_ipv4_or_registered_name`] ensures this is valid ASCII-US.
+    let value = unsafe { str::from_utf8_unchecked(value) };
+
+    match value.parse() {
+        Ok(ipv4) => Ok(Host::IPv4Address(ipv4)),
+        Err(_) => Ok(Host::RegisteredName(RegisteredName {
+            normalized,
+            registered_name: Cow::from(value),
+        })),
+    }
+}
+
+/// Parses an IP literal (including the surrounding brackets) from the given byte string.
+fn parse_ip_literal(value: &[u8]) -> Result<Host<'_>, HostError> {
+    if let Some(b'v') | Some(b'V') = value.get(1) {
+        // IPvFuture
+
+        let ipvfuture = parse_ipvfuture(&value[1..value.len() - 1])?;
+        return Ok(Host::IPvFuture(ipvfuture));
+    }
+
+    // IPv6
+
+    let ipv6 = &value[1..value.len() - 1];
+    let (ipv6, zone) = match ipv6.iter().position(|&byte| byte == b'%') {
+        Some(index) => match ipv6.get(index + 1..index + 3) {
+            Some(b"25") => (&ipv6[..index], Some(&ipv6[index + 3..])),
+            _ => return Err(HostError::InvalidIPv6Character),
+        },
+        None => (ipv6, None),
+    };
+
+    if !check_ipv6(ipv6) {
+        return Err(HostError::InvalidIPv6Character);
+    }
+
+    // Unsafe: The function above [`check_ipv6`] ensures this is valid ASCII-US.
+    let ipv6: Ipv6Addr = unsafe { str::from_utf8_unchecked(ipv6) }
+        .parse()
+        .map_err(|_| HostError::InvalidIPv6Format)?;
+
+    match zone {
+        Some(zone) => Ok(Host::IPv6AddressWithZone(
+            ipv6,
+            ZoneIdentifier::try_from(zone)?,
+        )),
+        None => Ok(Host::IPv6Address(ipv6)),
+    }
+}
+
+/// Parses a future IP literal (without the surrounding brackets) from the given byte string.
+fn parse_ipvfuture(value: &[u8]) -> Result<IPvFuture<'_>, HostError> {
+    let separator_index = match value.iter().position(|&byte| byte == b'.') {
+        Some(index) => index,
+        None => return Err(HostError::InvalidIPvFutureFormat),
+    };
+    let version = &value[1..separator_index];
+    let address = &value[separator_index + 1..];
+
+    if version.is_empty() || !version.iter().all(u8::is_ascii_hexdigit) || address.is_empty() {
+        return Err(HostError::InvalidIPvFutureFormat);
+    }
+
+    if !check_ipvfuture(address) {
+        return Err(HostError::InvalidIPvFutureCharacter);
+    }
+
+    // Unsafe: The checks above make sure the byte string is valid ASCII-US.
+    Ok(IPvFuture {
+        ipvfuture: Cow::from(unsafe { str::from_utf8_unchecked(value) }),
+        normalized: !value.iter().any(u8::is_ascii_uppercase),
+        separator_index,
+    })
+}
+
 /// Parses the port from the given byte string.
 pub fn parse_port(value: &[u8]) -> Result<Option<u16>, PortError> {
+    parse_port_with_index(value).map_err(|(error, _)| error)
+}
+
+/// Parses the port from the given byte string.
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+/// Overflows are reported at the start of the port.
+fn parse_port_with_index(value: &[u8]) -> Result<Option<u16>, (PortError, usize)> {
     if value.is_empty() {
         Ok(None)
     } else {
         let mut port = 0u16;
 
-        for &byte in value {
+        for (index, &byte) in value.iter().enumerate() {
             if !byte.is_ascii_digit() {
-                return Err(PortError::InvalidCharacter);
+                return Err((PortError::InvalidCharacter, index));
             }
 
-            port = port.checked_mul(10).ok_or(PortError::Overflow)?;
             port = port
-                .checked_add((byte - b'0').into())
-                .ok_or(PortError::Overflow)?;
+                .checked_mul(10)
+                .and_then(|port| port.checked_add((byte - b'0').into()))
+                .ok_or((PortError::Overflow, 0))?;
         }
 
         Ok(Some(port))
@@ -2236,7 +3888,11 @@ pub fn parse_port(value: &[u8]) -> Result<Option<u16>, PortError> {
 }
 
 /// Parses the user information from the given byte string.
-fn parse_user_info(value: &[u8]) -> Result<(Username, Option<Password>), UserInfoError> {
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+fn parse_user_info(
+    value: &[u8],
+) -> Result<(Username<'_>, Option<Password<'_>>), (UserInfoError, usize)> {
     let mut bytes = value.iter().enumerate();
     let mut first_colon_index = None;
     let mut password_normalized = true;
@@ -2246,9 +3902,9 @@ fn parse_user_info(value: &[u8]) -> Result<(Username, Option<Password>), UserInf
         match USER_INFO_CHAR_MAP[byte as usize] {
             0 => {
                 return if first_colon_index.is_some() {
-                    Err(PasswordError::InvalidCharacter.into())
+                    Err((PasswordError::InvalidCharacter.into(), index))
                 } else {
-                    Err(UsernameError::InvalidCharacter.into())
+                    Err((UsernameError::InvalidCharacter.into(), index))
                 }
             }
             b'%' => match get_percent_encoded_value(
@@ -2266,16 +3922,14 @@ fn parse_user_info(value: &[u8]) -> Result<(Username, Option<Password>), UserInf
                 }
                 Err(_) => {
                     return if first_colon_index.is_some() {
-                        Err(PasswordError::InvalidPercentEncoding.into())
+                        Err((PasswordError::InvalidPercentEncoding.into(), index))
                     } else {
-                        Err(UsernameError::InvalidPercentEncoding.into())
+                        Err((UsernameError::InvalidPercentEncoding.into(), index))
                     }
                 }
             },
-            b':' => {
-                if first_colon_index.is_none() {
-                    first_colon_index = Some(index);
-                }
+            b':' if first_colon_index.is_none() => {
+                first_colon_index = Some(index);
             }
             _ => (),
         }
@@ -2303,3 +3957,616 @@ fn parse_user_info(value: &[u8]) -> Result<(Username, Option<Password>), UserInf
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::format;
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+
+    use super::*;
+
+    #[test]
+    fn test_authority_without() {
+        let authority = Authority::try_from("user:pass@example.com:8080").unwrap();
+
+        let without_userinfo = authority.without_userinfo();
+        assert!(!without_userinfo.has_username());
+        assert!(!without_userinfo.has_password());
+        assert_eq!(without_userinfo.host(), authority.host());
+        assert_eq!(without_userinfo.port(), Some(8080));
+        assert_eq!(without_userinfo.to_string(), "example.com:8080");
+
+        let without_port = authority.without_port();
+        assert_eq!(without_port.username().unwrap(), "user");
+        assert_eq!(without_port.password().unwrap(), "pass");
+        assert_eq!(without_port.host(), authority.host());
+        assert!(!without_port.has_port());
+        assert_eq!(without_port.to_string(), "user:pass@example.com");
+
+        let stripped = authority.without_userinfo().without_port();
+        assert_eq!(stripped.to_string(), "example.com");
+        assert_eq!(authority.to_string(), "user:pass@example.com:8080");
+    }
+
+    #[test]
+    fn test_authority_display() {
+        fn test_case(value: &str, redacted: &str) {
+            let authority = Authority::try_from(value).unwrap();
+            assert_eq!(format!("{}", authority), value);
+            assert_eq!(format!("{:#}", authority), redacted);
+        }
+
+        test_case("u:p@host:8080", "***@host:8080");
+        test_case("u@host", "***@host");
+        test_case("@host", "***@host");
+        test_case("host:8080", "host:8080");
+        test_case("[::1]", "[::1]");
+    }
+
+    #[test]
+    fn test_authority_host_port() {
+        fn hash(authority: &Authority, state: &RandomState) -> u64 {
+            let mut hasher = state.build_hasher();
+            authority.hash_host_port(&mut hasher);
+            hasher.finish()
+        }
+
+        let state = RandomState::new();
+        let first = Authority::try_from("u1@host:80").unwrap();
+        let second = Authority::try_from("u2:pass@HOST:80").unwrap();
+        let third = Authority::try_from("host:81").unwrap();
+        let fourth = Authority::try_from("host").unwrap();
+
+        assert!(first.eq_host_port(&second));
+        assert_eq!(hash(&first, &state), hash(&second, &state));
+        assert_ne!(first, second);
+
+        assert!(!first.eq_host_port(&third));
+        assert_ne!(hash(&first, &state), hash(&third, &state));
+        assert!(!first.eq_host_port(&fourth));
+        assert!(fourth.eq_host_port(&Authority::try_from("user@host").unwrap()));
+    }
+
+    #[test]
+    fn test_authority_port_or_default() {
+        let unknown = Scheme::try_from("test-scheme").unwrap();
+
+        let authority = Authority::try_from("example.com:8080").unwrap();
+        assert_eq!(authority.port_or_default(&Scheme::HTTP), Some(8080));
+        assert_eq!(authority.port_or_default(&unknown), Some(8080));
+
+        let authority = Authority::try_from("example.com").unwrap();
+        assert_eq!(authority.port_or_default(&Scheme::HTTP), Some(80));
+        assert_eq!(authority.port_or_default(&Scheme::HTTPS), Some(443));
+        assert_eq!(authority.port_or_default(&unknown), None);
+    }
+
+    #[test]
+    fn test_authority_user_info() {
+        let authority = Authority::try_from("user%40example.com:p%40ss@example.com").unwrap();
+        let username = authority.username().unwrap();
+        let password = authority.password().unwrap();
+
+        assert_eq!(username.decode(), "user@example.com");
+        assert_eq!(password.decode(), "p@ss");
+        assert!(matches!(
+            Username::try_from("user").unwrap().decode(),
+            Cow::Borrowed("user")
+        ));
+
+        assert_eq!(format!("{:?}", password), "Password(\"***\")");
+        assert!(!format!("{:?}", authority).contains("p%40ss"));
+        assert_eq!(password.to_string(), "p%40ss");
+        assert_eq!(password.as_str(), "p%40ss");
+    }
+
+    #[test]
+    fn test_host_as_ip() {
+        let host = Host::try_from("192.168.1.1").unwrap();
+        assert_eq!(host.as_ipv4(), Some(Ipv4Addr::new(192, 168, 1, 1)));
+        assert_eq!(host.as_ipv6(), None);
+        assert_eq!(
+            host.as_ip_addr(),
+            Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+        );
+
+        let host = Host::try_from("[2001:db8::1]").unwrap();
+        let address = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        assert_eq!(host.as_ipv4(), None);
+        assert_eq!(host.as_ipv6(), Some(address));
+        assert_eq!(host.as_ip_addr(), Some(IpAddr::V6(address)));
+
+        let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+        let address = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1);
+        assert_eq!(host.as_ipv6(), Some(address));
+        assert_eq!(host.as_ip_addr(), Some(IpAddr::V6(address)));
+
+        let host = Host::try_from("example.com").unwrap();
+        assert_eq!(host.as_ipv4(), None);
+        assert_eq!(host.as_ipv6(), None);
+        assert_eq!(host.as_ip_addr(), None);
+    }
+
+    #[test]
+    fn test_authority_parse_with_policy() {
+        fn test_case(value: &str, policy: &AuthorityPolicy, expected: Result<(), AuthorityError>) {
+            assert_eq!(
+                Authority::parse_with_policy(value.as_bytes(), policy).map(|_| ()),
+                expected
+            );
+        }
+
+        let permissive = AuthorityPolicy::default();
+        let forbid_user_info = AuthorityPolicy {
+            forbid_user_info: true,
+            ..AuthorityPolicy::default()
+        };
+        let require_host = AuthorityPolicy {
+            require_host: true,
+            ..AuthorityPolicy::default()
+        };
+
+        test_case("u:p@host", &permissive, Ok(()));
+        test_case("", &permissive, Ok(()));
+        test_case(":80", &permissive, Ok(()));
+        test_case(
+            "u:p@host",
+            &forbid_user_info,
+            Err(AuthorityError::UserInfoNotAllowed),
+        );
+        test_case(
+            "u@host",
+            &forbid_user_info,
+            Err(AuthorityError::UserInfoNotAllowed),
+        );
+        test_case(
+            "@host",
+            &forbid_user_info,
+            Err(AuthorityError::UserInfoNotAllowed),
+        );
+        test_case("host:80", &forbid_user_info, Ok(()));
+        test_case("", &forbid_user_info, Ok(()));
+        test_case("", &require_host, Err(AuthorityError::MissingHost));
+        test_case(":80", &require_host, Err(AuthorityError::MissingHost));
+        test_case("u@", &require_host, Err(AuthorityError::MissingHost));
+        test_case("u:p@host", &require_host, Ok(()));
+        test_case("[::1]", &require_host, Ok(()));
+        test_case(
+            "host:x",
+            &require_host,
+            Err(AuthorityError::Port(PortError::InvalidCharacter)),
+        );
+    }
+
+    #[test]
+    fn test_host_eq_fqdn() {
+        fn test_case(left: &str, right: &str, expected: bool) {
+            let left = Host::try_from(left).unwrap();
+            let right = Host::try_from(right).unwrap();
+            assert_eq!(left.eq_fqdn(&right), expected);
+            assert_eq!(right.eq_fqdn(&left), expected);
+        }
+
+        test_case("example.com", "example.com.", true);
+        test_case("example.com.", "example.com.", true);
+        test_case("example.com", "EXAMPLE.COM.", true);
+        test_case("ex%61mple.com", "example.com.", true);
+        test_case("example.com", "example.com..", false);
+        test_case("example.com", "example.org.", false);
+        test_case("example.com", "example.org", false);
+        test_case("127.0.0.1", "127.0.0.1", true);
+        test_case("127.0.0.1", "127.0.0.1.", false);
+        test_case("[::1]", "[::1]", true);
+
+        assert_ne!(
+            Host::try_from("example.com").unwrap(),
+            Host::try_from("example.com.").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_host_is_empty() {
+        let host = Host::try_from("").unwrap();
+        assert!(host.is_empty());
+        assert!(host.is_registered_name());
+        assert_eq!(host.to_string(), "");
+
+        assert!(!Host::try_from("a").unwrap().is_empty());
+        assert!(!Host::try_from("%20").unwrap().is_empty());
+        assert!(!Host::try_from("0.0.0.0").unwrap().is_empty());
+        assert!(!Host::try_from("[::]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_host_from_ip() {
+        let address = Ipv4Addr::new(192, 168, 1, 1);
+        let host = Host::from(address);
+        assert_eq!(host.to_string(), "192.168.1.1");
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+        assert_eq!(Host::from(IpAddr::V4(address)), host);
+
+        let address = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        let host = Host::from(address);
+        assert_eq!(host.to_string(), "[2001:db8::1]");
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+        assert_eq!(Host::from(IpAddr::V6(address)), host);
+
+        let authority = Authority::from_parts(
+            None::<Username>,
+            None::<Password>,
+            Host::from(Ipv6Addr::LOCALHOST),
+            Some(8080),
+        )
+        .unwrap();
+        assert_eq!(authority.to_string(), "[::1]:8080");
+    }
+
+    #[test]
+    fn test_host_ipvfuture() {
+        let host = Host::try_from("[v1.fe80::a+en1]").unwrap();
+        assert!(host.is_ipvfuture());
+        assert_eq!(host.as_ip_addr(), None);
+        assert_eq!(host.to_string(), "[v1.fe80::a+en1]");
+
+        let ipvfuture = host.as_ipvfuture().unwrap();
+        assert_eq!(ipvfuture.version(), "1");
+        assert_eq!(ipvfuture.address(), "fe80::a+en1");
+        assert!(ipvfuture.is_normalized());
+
+        let mut host = Host::try_from("[VFF.Address]").unwrap();
+        assert_eq!(host.as_ipvfuture().unwrap().version(), "FF");
+        assert_eq!(host, Host::try_from("[vff.address]").unwrap());
+        assert!(!host.is_normalized());
+        host.normalize();
+        assert_eq!(host.to_string(), "[vff.address]");
+
+        let authority = Authority::try_from("user@[v7.a:b]:8080").unwrap();
+        assert_eq!(authority.host().as_ipvfuture().unwrap().address(), "a:b");
+        assert_eq!(authority.port(), Some(8080));
+        assert_eq!(authority.to_string(), "user@[v7.a:b]:8080");
+
+        assert_eq!(
+            Host::try_from("[v.fe80::a]"),
+            Err(HostError::InvalidIPvFutureFormat)
+        );
+        assert_eq!(
+            Host::try_from("[vg.fe80::a]"),
+            Err(HostError::InvalidIPvFutureFormat)
+        );
+        assert_eq!(
+            Host::try_from("[v1]"),
+            Err(HostError::InvalidIPvFutureFormat)
+        );
+        assert_eq!(
+            Host::try_from("[v1.]"),
+            Err(HostError::InvalidIPvFutureFormat)
+        );
+        assert_eq!(
+            Host::try_from("[v1.fe80%25en1]"),
+            Err(HostError::InvalidIPvFutureCharacter)
+        );
+        assert_eq!(
+            Host::try_from("[v1.a/b]"),
+            Err(HostError::InvalidIPvFutureCharacter)
+        );
+    }
+
+    #[cfg(feature = "idna")]
+    #[test]
+    fn test_host_idna() {
+        let host = Host::try_from("m%C3%BCnchen.de").unwrap();
+        let ascii = host.to_ascii().unwrap();
+        assert_eq!(ascii.to_string(), "xn--mnchen-3ya.de");
+        assert_eq!(ascii.to_unicode().unwrap(), "münchen.de");
+
+        let host = Host::try_from("example.com").unwrap();
+        assert_eq!(host.to_ascii().unwrap(), host);
+        assert_eq!(host.to_unicode().unwrap(), "example.com");
+
+        let host = Host::try_from("[::1]").unwrap();
+        assert_eq!(host.to_ascii().unwrap(), host);
+        assert_eq!(host.to_unicode().unwrap(), "[::1]");
+
+        let host = Host::try_from("127.0.0.1").unwrap();
+        assert_eq!(host.to_ascii().unwrap(), host);
+
+        let host = Host::try_from("%FF.com").unwrap();
+        assert_eq!(host.to_ascii(), Err(IDNAError::InvalidUTF8));
+    }
+
+    #[test]
+    fn test_host_is_loopback() {
+        fn test_case(value: &str, expected: bool) {
+            assert_eq!(Host::try_from(value).unwrap().is_loopback(), expected);
+        }
+
+        test_case("127.0.0.1", true);
+        test_case("127.255.255.254", true);
+        test_case("[::1]", true);
+        test_case("[::ffff:127.0.0.1]", true);
+        test_case("[::ffff:7f00:2]", true);
+        test_case("localhost", true);
+        test_case("LocalHost", true);
+        test_case("128.0.0.1", false);
+        test_case("[::2]", false);
+        test_case("[::ffff:128.0.0.1]", false);
+        test_case("localhost.example.com", false);
+    }
+
+    #[test]
+    fn test_host_is_private() {
+        fn test_case(value: &str, expected: bool) {
+            assert_eq!(Host::try_from(value).unwrap().is_private(), expected);
+        }
+
+        test_case("10.0.0.1", true);
+        test_case("10.255.255.255", true);
+        test_case("172.16.0.1", true);
+        test_case("172.31.255.255", true);
+        test_case("192.168.0.1", true);
+        test_case("169.254.1.1", true);
+        test_case("[fc00::1]", true);
+        test_case("[fdff::1]", true);
+        test_case("[fe80::1]", true);
+        test_case("[fe80::1%25eth0]", true);
+        test_case("[febf::1]", true);
+        test_case("[::ffff:10.0.0.1]", true);
+        test_case("[::ffff:192.168.0.1]", true);
+        test_case("[::ffff:169.254.1.1]", true);
+        test_case("9.255.255.255", false);
+        test_case("172.15.255.255", false);
+        test_case("172.32.0.1", false);
+        test_case("192.169.0.1", false);
+        test_case("8.8.8.8", false);
+        test_case("[fe00::1]", false);
+        test_case("[fec0::1]", false);
+        test_case("[2001:db8::1]", false);
+        test_case("[::1]", false);
+        test_case("[::ffff:8.8.8.8]", false);
+        test_case("example.com", false);
+    }
+
+    #[test]
+    fn test_host_ipv4_mapped_ipv6() {
+        fn test_case(value: &str, expected: &str) {
+            let mut host = Host::try_from(value).unwrap();
+            let address = Ipv4Addr::new(192, 168, 0, 1).to_ipv6_mapped();
+            assert!(host.is_ipv6_address());
+            assert_eq!(host.as_ipv6(), Some(address));
+            assert_eq!(host.as_ip_addr(), Some(IpAddr::V6(address)));
+            assert_eq!(host.as_ipv4(), None);
+
+            host.normalize();
+            assert!(host.is_normalized());
+            assert_eq!(host.to_string(), expected);
+            assert_eq!(Host::try_from(expected).unwrap(), host);
+        }
+
+        test_case("[::ffff:192.168.0.1]", "[::ffff:192.168.0.1]");
+        test_case("[::FFFF:192.168.0.1]", "[::ffff:192.168.0.1]");
+        test_case("[0:0:0:0:0:ffff:192.168.0.1]", "[::ffff:192.168.0.1]");
+        test_case(
+            "[0000:0000:0000:0000:0000:FFFF:C0A8:0001]",
+            "[::ffff:192.168.0.1]",
+        );
+        test_case("[::ffff:c0a8:1]", "[::ffff:192.168.0.1]");
+        test_case("[::ffff:192.168.0.1%25eth0]", "[::ffff:192.168.0.1%25eth0]");
+
+        let mut authority = Authority::try_from("[::FFFF:192.168.0.1]:8080").unwrap();
+        authority.normalize();
+        assert_eq!(authority.to_string(), "[::ffff:192.168.0.1]:8080");
+
+        assert_eq!(
+            Host::try_from("[::ffff:192.168.0.256]"),
+            Err(HostError::InvalidIPv6Format)
+        );
+        assert_eq!(
+            Host::try_from("[::ffff:192.168.0.1:1]"),
+            Err(HostError::InvalidIPv6Format)
+        );
+    }
+
+    #[test]
+    fn test_host_ipv6_normalize() {
+        fn test_case(value: &str, expected: &str) {
+            let mut host = Host::try_from(value).unwrap();
+            assert!(host.is_normalized());
+            host.normalize();
+            assert_eq!(host.to_string(), expected);
+        }
+
+        test_case("[2001:0db8:0000:0000:0000:0000:0000:0001]", "[2001:db8::1]");
+        test_case("[2001:DB8::1]", "[2001:db8::1]");
+        test_case("[0:0:0:0:0:0:0:0]", "[::]");
+        test_case("[2001:db8:0:1:1:1:1:1]", "[2001:db8:0:1:1:1:1:1]");
+        test_case("[2001:db8:0:0:1:0:0:0]", "[2001:db8:0:0:1::]");
+        test_case("[2001:db8:0:0:1:0:0:1]", "[2001:db8::1:0:0:1]");
+        test_case("[2001:0:0:1:0:0:0:1]", "[2001:0:0:1::1]");
+        test_case("[::ffff:192.168.0.1]", "[::ffff:192.168.0.1]");
+        test_case("[0:0:0:0:0:FFFF:C0A8:0001]", "[::ffff:192.168.0.1]");
+
+        assert_eq!(
+            Host::try_from("[::ffff:192.168.0]"),
+            Err(HostError::InvalidIPv6Format)
+        );
+    }
+
+    #[test]
+    fn test_host_normalize() {
+        fn test_case(value: &str, expected: &str, normalized: bool) {
+            let mut host = Host::try_from(value).unwrap();
+            assert_eq!(host.is_normalized(), normalized);
+            host.normalize();
+            assert!(host.is_normalized());
+            assert_eq!(host.to_string(), expected);
+        }
+
+        test_case("ExAmPle.COM", "example.com", false);
+        test_case("ex%41mple.com", "example.com", false);
+        test_case("ex%4Dmple.com", "exmmple.com", false);
+        test_case("caf%c3%a9.com", "caf%C3%A9.com", false);
+        test_case("example.com", "example.com", true);
+        test_case("127.0.0.1", "127.0.0.1", true);
+        test_case("[v1.ABC]", "[v1.abc]", false);
+        test_case("[fe80::1%25ETH0]", "[fe80::1%25ETH0]", true);
+    }
+
+    #[test]
+    fn test_host_registered_name_from_unencoded() {
+        let host = Host::registered_name_from_unencoded("my host");
+        assert_eq!(host.to_string(), "my%20host");
+        assert!(host.is_normalized());
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+
+        let host = Host::registered_name_from_unencoded("a/b@c:d");
+        assert_eq!(host.to_string(), "a%2Fb%40c%3Ad");
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+
+        let host = Host::registered_name_from_unencoded("100%.Example");
+        assert_eq!(host.to_string(), "100%25.Example");
+        assert!(!host.is_normalized());
+
+        let host = Host::registered_name_from_unencoded("m\u{fc}nchen.de");
+        assert_eq!(host.to_string(), "m%C3%BCnchen.de");
+        assert!(host.is_normalized());
+
+        let host = Host::registered_name_from_unencoded("127.0.0.1");
+        assert!(host.is_ipv4_address());
+        assert!(host.is_loopback());
+        assert_eq!(host.to_string(), "127.0.0.1");
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+
+        let host = Host::registered_name_from_unencoded("127.0.0");
+        assert!(host.is_registered_name());
+        assert_eq!(Host::try_from(host.to_string().as_str()).unwrap(), host);
+
+        let authority = Authority::from_parts(
+            None::<Username>,
+            None::<Password>,
+            Host::registered_name_from_unencoded("[::1]"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(authority.to_string(), "%5B%3A%3A1%5D");
+    }
+
+    #[test]
+    fn test_host_to_string_bracketed() {
+        fn test_case(value: &str, bracketed: &str, unbracketed: &str) {
+            let host = Host::try_from(value).unwrap();
+            assert_eq!(host.to_string_bracketed(), bracketed);
+            assert_eq!(host.to_string_unbracketed(), unbracketed);
+        }
+
+        test_case("[::1]", "[::1]", "::1");
+        test_case("[2001:DB8::1]", "[2001:db8::1]", "2001:db8::1");
+        test_case("[fe80::1%25eth0]", "[fe80::1%25eth0]", "fe80::1%eth0");
+        test_case("[fe80::1%25%65th0]", "[fe80::1%25%65th0]", "fe80::1%eth0");
+        test_case("[v7.test]", "[v7.test]", "v7.test");
+        test_case("127.0.0.1", "127.0.0.1", "127.0.0.1");
+        test_case("example.com", "example.com", "example.com");
+        test_case("", "", "");
+    }
+
+    #[test]
+    fn test_host_zone_identifier() {
+        let host = Host::try_from("[fe80::1%25eth0]").unwrap();
+        assert!(host.is_ipv6_address());
+        assert_eq!(host.zone_identifier(), Some("eth0"));
+        assert_eq!(host.to_string(), "[fe80::1%25eth0]");
+
+        let host = Host::try_from("[fe80::1%25%65th0]").unwrap();
+        assert_eq!(host.zone_identifier(), Some("%65th0"));
+        assert_eq!(host.to_string(), "[fe80::1%25%65th0]");
+        assert_eq!(host, Host::try_from("[fe80::1%25eth0]").unwrap());
+
+        let host = Host::try_from("[::1]").unwrap();
+        assert_eq!(host.zone_identifier(), None);
+        assert_eq!(
+            Host::try_from("example.com").unwrap().zone_identifier(),
+            None
+        );
+
+        let authority = Authority::try_from("[fe80::1%25eth0]:8080").unwrap();
+        assert_eq!(authority.host().zone_identifier(), Some("eth0"));
+        assert_eq!(authority.port(), Some(8080));
+        assert_eq!(authority.to_string(), "[fe80::1%25eth0]:8080");
+
+        assert_eq!(
+            Host::try_from("[fe80::1%25]"),
+            Err(HostError::ZoneIdentifier(ZoneIdentifierError::Empty))
+        );
+        assert_eq!(
+            Host::try_from("[fe80::1%25eth/0]"),
+            Err(HostError::ZoneIdentifier(
+                ZoneIdentifierError::InvalidCharacter
+            ))
+        );
+        assert_eq!(
+            Host::try_from("[fe80::1%eth0]"),
+            Err(HostError::InvalidIPv6Character)
+        );
+    }
+
+    #[test]
+    fn test_user_info() {
+        let user_info = UserInfo::new("user", Some("pass")).unwrap();
+        assert_eq!(user_info.username(), "user");
+        assert_eq!(user_info.password().unwrap(), "pass");
+        assert_eq!(user_info.to_string(), "user:pass");
+
+        let user_info = UserInfo::new("user", None::<&str>).unwrap();
+        assert_eq!(user_info.username(), "user");
+        assert!(user_info.password().is_none());
+        assert_eq!(user_info.to_string(), "user");
+
+        let user_info = UserInfo::try_from("user:p%40ss").unwrap();
+        assert_eq!(user_info.password().unwrap().decode(), "p@ss");
+        assert_eq!(user_info.to_string(), "user:p%40ss");
+
+        let authority = Authority::try_from("user:p%40ss@example.com").unwrap();
+        assert_eq!(authority.user_info().unwrap(), user_info);
+        assert!(Authority::try_from("example.com")
+            .unwrap()
+            .user_info()
+            .is_none());
+
+        assert_eq!(
+            UserInfo::new("us:er", None::<&str>),
+            Err(UserInfoError::Username(UsernameError::ContainsColon))
+        );
+        assert_eq!(
+            UserInfo::new("user", Some("p@ss")),
+            Err(UserInfoError::Password(PasswordError::InvalidCharacter))
+        );
+    }
+
+    #[test]
+    fn test_user_info_ct_eq() {
+        fn test_case(left: &str, right: &str) {
+            let left = UserInfo::try_from(left).unwrap();
+            let right = UserInfo::try_from(right).unwrap();
+            assert_eq!(left.ct_eq(&right), left == right);
+            assert_eq!(right.ct_eq(&left), left == right);
+
+            if let (Some(left), Some(right)) = (left.password(), right.password()) {
+                assert_eq!(left.ct_eq(right), left == right);
+            }
+        }
+
+        test_case("user:pass", "user:pass");
+        test_case("user:pass", "user:p%61ss");
+        test_case("user:p%40ss", "user:p%40ss");
+        test_case("user:pass", "user:Pass");
+        test_case("user:pass", "user:passw");
+        test_case("user:pass", "User:pass");
+        test_case("user:pass", "user");
+        test_case("user:", "user");
+        test_case("user", "user");
+
+        let password = Password::try_from("a%3Ab").unwrap();
+        assert!(password.ct_eq(&Password::try_from("a:b").unwrap()));
+        assert_ne!(password, "a:b");
+    }
+}
@@ -0,0 +1,355 @@
+//! Data URIs
+//!
+//! See [RFC2397](https://tools.ietf.org/html/rfc2397).
+//!
+//! A data URI embeds its content directly in the URI, such as in `"data:text/plain;base64,SGk="`.
+//! Since the content is just the path (and possibly the query) of the URI as far as
+//! [RFC3986](https://tools.ietf.org/html/rfc3986) is concerned, [`DataURI`] can be used to get the
+//! media type and the decoded content out of it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! use uriparse::{DataURI, URI};
+//!
+//! let uri = URI::try_from("data:text/plain;base64,SGk=").unwrap();
+//! let data_uri = DataURI::try_from(&uri).unwrap();
+//! assert_eq!(data_uri.media_type(), "text/plain");
+//! assert!(data_uri.is_base64());
+//! assert_eq!(data_uri.data(), b"Hi");
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::scheme::Scheme;
+use crate::uri::URI;
+use crate::utility::{decode_percent_encoded, percent_decode};
+
+/// The media type of a data URI that does not specify one.
+const DEFAULT_MEDIA_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// A data URI as defined in [RFC2397](https://tools.ietf.org/html/rfc2397), with its content
+/// decoded.
+///
+/// This is created from a [`URI`] with the `data` scheme using [`DataURI::try_from`] or
+/// [`URI::as_data_uri`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DataURI {
+    /// Whether the content was base64-encoded.
+    base64: bool,
+
+    /// The decoded content.
+    data: Vec<u8>,
+
+    /// The percent-decoded media type, including its parameters.
+    media_type: String,
+}
+
+impl DataURI {
+    /// Returns the decoded content of the data URI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{DataURI, URI};
+    ///
+    /// let uri = URI::try_from("data:,a%20b").unwrap();
+    /// assert_eq!(DataURI::try_from(&uri).unwrap().data(), b"a b");
+    /// ```
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consumes the data URI and returns its decoded content.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns whether the content of the data URI was base64-encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{DataURI, URI};
+    ///
+    /// let uri = URI::try_from("data:;base64,SGk=").unwrap();
+    /// assert!(DataURI::try_from(&uri).unwrap().is_base64());
+    ///
+    /// let uri = URI::try_from("data:,Hi").unwrap();
+    /// assert!(!DataURI::try_from(&uri).unwrap().is_base64());
+    /// ```
+    pub fn is_base64(&self) -> bool {
+        self.base64
+    }
+
+    /// Returns the media type of the data URI, including its parameters (e.g. `";charset=utf-8"`).
+    ///
+    /// If the data URI does not specify a media type, the default of `"text/plain"` is used. If it
+    /// does not specify any parameters either, the default of `"text/plain;charset=US-ASCII"` is
+    /// used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{DataURI, URI};
+    ///
+    /// let uri = URI::try_from("data:image/png;base64,").unwrap();
+    /// assert_eq!(DataURI::try_from(&uri).unwrap().media_type(), "image/png");
+    ///
+    /// let uri = URI::try_from("data:;charset=utf-8,").unwrap();
+    /// assert_eq!(
+    ///     DataURI::try_from(&uri).unwrap().media_type(),
+    ///     "text/plain;charset=utf-8"
+    /// );
+    ///
+    /// let uri = URI::try_from("data:,").unwrap();
+    /// assert_eq!(
+    ///     DataURI::try_from(&uri).unwrap().media_type(),
+    ///     "text/plain;charset=US-ASCII"
+    /// );
+    /// ```
+    pub fn media_type(&self) -> &str {
+        &self.media_type
+    }
+}
+
+impl<'uri> TryFrom<&URI<'uri>> for DataURI {
+    type Error = DataURIError;
+
+    fn try_from(value: &URI<'uri>) -> Result<Self, Self::Error> {
+        if value.scheme() != &Scheme::Data {
+            return Err(DataURIError::NotDataURI);
+        }
+
+        let value = value.scheme_specific_part();
+        let comma_index = value.find(',').ok_or(DataURIError::MissingComma)?;
+        let header = decode_percent_encoded(&value[..comma_index]);
+        let mut media_type = header.trim();
+        let base64 = match media_type.len().checked_sub(7) {
+            Some(index)
+                if media_type.is_char_boundary(index)
+                    && media_type[index..].eq_ignore_ascii_case(";base64") =>
+            {
+                media_type = media_type[..index].trim_end();
+                true
+            }
+            _ => false,
+        };
+
+        let media_type = if media_type.is_empty() {
+            String::from(DEFAULT_MEDIA_TYPE)
+        } else if media_type.starts_with(';') {
+            let mut value = String::from("text/plain");
+            value.push_str(media_type);
+            value
+        } else {
+            String::from(media_type)
+        };
+
+        let data = percent_decode(value[comma_index + 1..].as_bytes())
+            .expect("invalid percent-encoding in URI")
+            .into_owned();
+        let data = if base64 {
+            decode_base64(&data).ok_or(DataURIError::InvalidBase64)?
+        } else {
+            data
+        };
+
+        Ok(DataURI {
+            base64,
+            data,
+            media_type,
+        })
+    }
+}
+
+/// An error representing a URI that is not a valid data URI.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DataURIError {
+    /// The content was marked as base64-encoded, but it is not valid base64.
+    InvalidBase64,
+
+    /// The URI does not contain the `','` separating the media type from the content.
+    MissingComma,
+
+    /// The URI does not have the `data` scheme.
+    NotDataURI,
+}
+
+impl Display for DataURIError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::DataURIError::*;
+
+        match self {
+            InvalidBase64 => write!(formatter, "invalid data URI base64 content"),
+            MissingComma => write!(formatter, "missing data URI comma"),
+            NotDataURI => write!(formatter, "not data URI"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DataURIError {}
+
+/// Decodes the given base64 bytes using the standard alphabet.
+///
+/// ASCII whitespace is ignored and padding is optional, but if present, it must be correct.
+/// Returns `None` if the bytes are not valid base64.
+fn decode_base64(value: &[u8]) -> Option<Vec<u8>> {
+    let mut value: Vec<u8> = value
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+
+    let remainder = value.len() % 4;
+
+    if remainder == 0 {
+        for _ in 0..2 {
+            if value.last() == Some(&b'=') {
+                value.pop();
+            }
+        }
+    }
+
+    if value.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut decoded = Vec::with_capacity(value.len() / 4 * 3 + 2);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for byte in value {
+        let sextet = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+
+        buffer = (buffer << 6) | u32::from(sextet);
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_data_uri() {
+        fn test_case(value: &str, media_type: &str, base64: bool, data: &[u8]) {
+            let uri = URI::try_from(value).unwrap();
+            let data_uri = DataURI::try_from(&uri).unwrap();
+            assert_eq!(data_uri.media_type(), media_type);
+            assert_eq!(data_uri.is_base64(), base64);
+            assert_eq!(data_uri.data(), data);
+        }
+
+        test_case("data:text/plain;base64,SGk=", "text/plain", true, b"Hi");
+        test_case("data:text/plain;base64,SGk", "text/plain", true, b"Hi");
+        test_case(
+            "data:text/plain;BASE64,SGVsbG8=",
+            "text/plain",
+            true,
+            b"Hello",
+        );
+        test_case(
+            "data:;base64,SGVs%20bG8",
+            DEFAULT_MEDIA_TYPE,
+            true,
+            b"Hello",
+        );
+        test_case("data:;base64,", DEFAULT_MEDIA_TYPE, true, b"");
+        test_case(
+            "data:application/octet-stream;base64,AP8=",
+            "application/octet-stream",
+            true,
+            b"\x00\xFF",
+        );
+        test_case(
+            "data:,Hello%2C%20World!",
+            DEFAULT_MEDIA_TYPE,
+            false,
+            b"Hello, World!",
+        );
+        test_case("data:,%FF", DEFAULT_MEDIA_TYPE, false, b"\xFF");
+        test_case("data:,a?b", DEFAULT_MEDIA_TYPE, false, b"a?b");
+        test_case("data:,a#b", DEFAULT_MEDIA_TYPE, false, b"a");
+        test_case("data:text/html,%3Ch1%3E", "text/html", false, b"<h1>");
+        test_case(
+            "data:text/plain;charset=utf-8,caf%C3%A9",
+            "text/plain;charset=utf-8",
+            false,
+            "caf\u{e9}".as_bytes(),
+        );
+        test_case(
+            "data:;charset=utf-8,",
+            "text/plain;charset=utf-8",
+            false,
+            b"",
+        );
+        test_case(
+            "data:text/plain;base64x,SGk=",
+            "text/plain;base64x",
+            false,
+            b"SGk=",
+        );
+        test_case("DATA:image/gif,", "image/gif", false, b"");
+    }
+
+    #[test]
+    fn test_data_uri_error() {
+        fn test_case(value: &str, expected: DataURIError) {
+            let uri = URI::try_from(value).unwrap();
+            assert_eq!(DataURI::try_from(&uri), Err(expected));
+        }
+
+        test_case("http://example.com/,a", DataURIError::NotDataURI);
+        test_case("data:text/plain", DataURIError::MissingComma);
+        test_case("data:;base64,SGk=x", DataURIError::InvalidBase64);
+        test_case("data:;base64,S", DataURIError::InvalidBase64);
+        test_case("data:;base64,SG=k", DataURIError::InvalidBase64);
+        test_case("data:;base64,SGk%3D%3D%3D", DataURIError::InvalidBase64);
+    }
+
+    #[test]
+    fn test_decode_base64() {
+        assert_eq!(decode_base64(b""), Some(vec![]));
+        assert_eq!(decode_base64(b"Zg=="), Some(b"f".to_vec()));
+        assert_eq!(decode_base64(b"Zm8="), Some(b"fo".to_vec()));
+        assert_eq!(decode_base64(b"Zm9v"), Some(b"foo".to_vec()));
+        assert_eq!(decode_base64(b"Zm9vYg"), Some(b"foob".to_vec()));
+        assert_eq!(decode_base64(b"Zm9v\nYmFy"), Some(b"foobar".to_vec()));
+        assert_eq!(decode_base64(b"+/+/"), Some(vec![0xFB, 0xFF, 0xBF]));
+        assert_eq!(decode_base64(b"Zg="), None);
+        assert_eq!(decode_base64(b"Zm9vY"), None);
+        assert_eq!(decode_base64(b"Zm-_"), None);
+    }
+}
@@ -2,22 +2,30 @@
 //!
 //! See [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)].
 
-use std::borrow::Cow;
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::{self, FromStr};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::ops::Deref;
-use std::str;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::utility::{
-    get_percent_encoded_value, normalize_string, percent_encoded_equality, percent_encoded_hash,
+    decode_percent_encoded, encode_percent_encoded, get_percent_encoded_value, normalize_string,
+    percent_decode, percent_encoded_cmp, percent_encoded_equality, percent_encoded_hash,
     UNRESERVED_CHAR_MAP,
 };
 
 /// A map of byte characters that determines if a character is a valid fragment character.
 #[rustfmt::skip]
-const FRAGMENT_CHAR_MAP: [u8; 256] = [
+pub(crate) const FRAGMENT_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -47,6 +55,9 @@ const FRAGMENT_CHAR_MAP: [u8; 256] = [
 /// However, be aware that just because percent-encoding plays no role in equality checking does not
 /// mean that the fragment is normalized. If the fragment needs to be normalized, use the
 /// [`Fragment::normalize`] function.
+///
+/// The fragment does not implement `Borrow<str>`, since its hash is not the same as the hash of the
+/// exact string. Use [`Fragment::as_str`] to look up a fragment in a map with string keys.
 #[derive(Clone, Debug)]
 pub struct Fragment<'fragment> {
     /// The internal fragment source that is either owned or borrowed.
@@ -56,9 +67,52 @@ pub struct Fragment<'fragment> {
     normalized: bool,
 }
 
+impl Fragment<'static> {
+    /// An empty fragment.
+    ///
+    /// Note that an empty fragment is different from no fragment at all, e.g. the URI
+    /// `"http://example.com/#"` has an empty fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// assert_eq!(Fragment::EMPTY, Fragment::try_from("").unwrap());
+    /// ```
+    pub const EMPTY: Fragment<'static> = Fragment {
+        fragment: Cow::Borrowed(""),
+        normalized: true,
+    };
+
+    /// Constructs a fragment from arbitrary text by percent-encoding all bytes that are not valid
+    /// fragment characters, including the `'%'` character itself.
+    ///
+    /// This is the inverse of [`Fragment::decode`] and always succeeds. The resulting fragment will
+    /// be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::from_unencoded("a b#c");
+    /// assert_eq!(fragment.as_str(), "a%20b%23c");
+    /// assert_eq!(fragment.decode(), "a b#c");
+    /// ```
+    pub fn from_unencoded(value: &str) -> Fragment<'static> {
+        Fragment {
+            fragment: Cow::Owned(encode_percent_encoded(value, &FRAGMENT_CHAR_MAP)),
+            normalized: true,
+        }
+    }
+}
+
 impl Fragment<'_> {
     /// Returns a new fragment which is identical but has a lifetime tied to this fragment.
-    pub fn as_borrowed(&self) -> Fragment {
+    pub fn as_borrowed(&self) -> Fragment<'_> {
         use self::Cow::*;
 
         let fragment = match &self.fragment {
@@ -88,6 +142,46 @@ impl Fragment<'_> {
         &self.fragment
     }
 
+    /// Returns the fragment with all percent-encoded bytes decoded.
+    ///
+    /// If the fragment contains no percent-encodings, the returned value will borrow from the
+    /// fragment. Decoded byte sequences that are not valid UTF-8 are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("a%20b").unwrap();
+    /// assert_eq!(fragment.decode(), "a b");
+    /// ```
+    pub fn decode(&self) -> Cow<'_, str> {
+        decode_percent_encoded(&self.fragment)
+    }
+
+    /// Returns the fragment with all percent-encoded bytes decoded, without requiring the decoded
+    /// bytes to be valid UTF-8.
+    ///
+    /// If the fragment contains no percent-encodings, the returned value will borrow from the
+    /// fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("a%FFb").unwrap();
+    /// assert_eq!(fragment.decode_bytes(), &b"a\xFFb"[..]);
+    /// ```
+    pub fn decode_bytes(&self) -> Cow<'_, [u8]> {
+        percent_decode(self.fragment.as_bytes()).unwrap()
+    }
+
     /// Converts the [`Fragment`] into an owned copy.
     ///
     /// If you construct the fragment from a source with a non-static lifetime, you may run into
@@ -103,6 +197,25 @@ impl Fragment<'_> {
         }
     }
 
+    /// Returns whether the fragment is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Fragment;
+    ///
+    /// let fragment = Fragment::try_from("").unwrap();
+    /// assert!(fragment.is_empty());
+    ///
+    /// let fragment = Fragment::try_from("fragment").unwrap();
+    /// assert!(!fragment.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.fragment.is_empty()
+    }
+
     /// Returns whether the fragment is normalized.
     ///
     /// A normalized fragment will have no bytes that are in the unreserved character set
@@ -129,6 +242,38 @@ impl Fragment<'_> {
         self.normalized
     }
 
+    /// Constructs a fragment from the given string without checking that it is valid.
+    ///
+    /// This skips the validation done by [`Fragment::try_from`], which is useful when the value is
+    /// already known to be valid (e.g. because it was taken from another fragment). If the value
+    /// contains percent-encodings, the fragment will not be considered normalized until
+    /// [`Fragment::normalize`] is called, even if it already is.
+    ///
+    /// # Safety
+    ///
+    /// The value must be a valid fragment, that is it must only consist of characters allowed in a
+    /// fragment as defined in
+    /// [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)], and every `'%'`
+    /// must be followed by two hexadecimal digits. Other functions of this crate, such as
+    /// [`Fragment::normalize`], rely on this to operate on the bytes of the fragment in place.
+    /// Passing an invalid value is undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Fragment;
+    ///
+    /// // Safety: The value is a valid fragment.
+    /// let fragment = unsafe { Fragment::new_unchecked("a%20b") };
+    /// assert_eq!(fragment.as_str(), "a%20b");
+    /// ```
+    pub unsafe fn new_unchecked(value: &str) -> Fragment<'_> {
+        Fragment {
+            normalized: !value.contains('%'),
+            fragment: Cow::Borrowed(value),
+        }
+    }
+
     /// Normalizes the fragment such that it will have no bytes that are in the unreserved character
     /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
@@ -155,7 +300,7 @@ impl Fragment<'_> {
     pub fn normalize(&mut self) {
         if !self.normalized {
             // Unsafe: Fragments must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.fragment.to_mut(), true) };
+            unsafe { normalize_string(self.fragment.to_mut(), true) };
             self.normalized = true;
         }
     }
@@ -181,6 +326,18 @@ impl Deref for Fragment<'_> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Fragment<'static> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        let fragment = Fragment::try_from(value.as_str()).map_err(de::Error::custom)?;
+        Ok(fragment.into_owned())
+    }
+}
+
 impl Display for Fragment<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         formatter.write_str(&self.fragment)
@@ -195,6 +352,14 @@ impl<'fragment> From<Fragment<'fragment>> for String {
     }
 }
 
+impl FromStr for Fragment<'static> {
+    type Err = FragmentError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Fragment::try_from(value.as_bytes())?.into_owned())
+    }
+}
+
 impl Hash for Fragment<'_> {
     fn hash<H>(&self, state: &mut H)
     where
@@ -204,6 +369,12 @@ impl Hash for Fragment<'_> {
     }
 }
 
+impl Ord for Fragment<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.fragment.as_bytes(), other.fragment.as_bytes(), true)
+    }
+}
+
 impl PartialEq for Fragment<'_> {
     fn eq(&self, other: &Fragment) -> bool {
         percent_encoded_equality(self.fragment.as_bytes(), other.fragment.as_bytes(), true)
@@ -228,7 +399,7 @@ impl<'a> PartialEq<&'a [u8]> for Fragment<'_> {
     }
 }
 
-impl<'a, 'fragment> PartialEq<Fragment<'fragment>> for &'a [u8] {
+impl<'fragment> PartialEq<Fragment<'fragment>> for &[u8] {
     fn eq(&self, other: &Fragment<'fragment>) -> bool {
         percent_encoded_equality(self, other.fragment.as_bytes(), true)
     }
@@ -252,30 +423,49 @@ impl<'a> PartialEq<&'a str> for Fragment<'_> {
     }
 }
 
-impl<'a, 'fragment> PartialEq<Fragment<'fragment>> for &'a str {
+impl<'fragment> PartialEq<Fragment<'fragment>> for &str {
     fn eq(&self, other: &Fragment<'fragment>) -> bool {
         percent_encoded_equality(self.as_bytes(), other.fragment.as_bytes(), true)
     }
 }
 
+impl PartialOrd for Fragment<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Fragment<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.fragment)
+    }
+}
+
 impl<'fragment> TryFrom<&'fragment [u8]> for Fragment<'fragment> {
     type Error = FragmentError;
 
     fn try_from(value: &'fragment [u8]) -> Result<Self, Self::Error> {
-        let mut bytes = value.iter();
+        let mut bytes = value.iter().enumerate();
         let mut normalized = true;
 
-        while let Some(&byte) = bytes.next() {
+        while let Some((index, &byte)) = bytes.next() {
             match FRAGMENT_CHAR_MAP[byte as usize] {
-                0 => return Err(FragmentError::InvalidCharacter),
+                0 => return Err(FragmentError::InvalidCharacter { byte, index }),
                 b'%' => {
-                    match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
+                    let first_digit = bytes.next().map(|(_, &digit)| digit);
+                    let second_digit = bytes.next().map(|(_, &digit)| digit);
+
+                    match get_percent_encoded_value(first_digit, second_digit) {
                         Ok((hex_value, uppercase)) => {
                             if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
                                 normalized = false;
                             }
                         }
-                        Err(_) => return Err(FragmentError::InvalidPercentEncoding),
+                        Err(_) => return Err(FragmentError::InvalidPercentEncoding { index }),
                     }
                 }
                 _ => (),
@@ -298,15 +488,55 @@ impl<'fragment> TryFrom<&'fragment str> for Fragment<'fragment> {
     }
 }
 
+/// The string is validated and then moved into the fragment without being copied.
+impl TryFrom<String> for Fragment<'static> {
+    type Error = FragmentError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let normalized = Fragment::try_from(value.as_str())?.normalized;
+
+        Ok(Fragment {
+            fragment: Cow::Owned(value),
+            normalized,
+        })
+    }
+}
+
+/// The byte string is validated and then moved into the fragment without being copied.
+impl TryFrom<Vec<u8>> for Fragment<'static> {
+    type Error = FragmentError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        let normalized = Fragment::try_from(value.as_slice())?.normalized;
+
+        // Unsafe: The validation above makes sure the byte string is valid ASCII-US.
+        Ok(Fragment {
+            fragment: Cow::Owned(unsafe { String::from_utf8_unchecked(value) }),
+            normalized,
+        })
+    }
+}
+
 /// An error representing an invalid fragment.
+///
+/// All indices are relative to the start of the fragment.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
 pub enum FragmentError {
     /// The fragment contained an invalid character.
-    InvalidCharacter,
+    InvalidCharacter {
+        /// The invalid byte.
+        byte: u8,
+
+        /// The index of the invalid byte.
+        index: usize,
+    },
 
     /// The fragment contained an invalid percent encoding (e.g. `"%ZZ"`).
-    InvalidPercentEncoding,
+    InvalidPercentEncoding {
+        /// The index of the `'%'` starting the invalid percent encoding.
+        index: usize,
+    },
 }
 
 impl Display for FragmentError {
@@ -314,35 +544,216 @@ impl Display for FragmentError {
         use self::FragmentError::*;
 
         match self {
-            InvalidCharacter => write!(formatter, "invalid fragment character"),
-            InvalidPercentEncoding => write!(formatter, "invalid fragment percent encoding"),
+            InvalidCharacter { byte, index } => write!(
+                formatter,
+                "invalid fragment character {:?} at index {}",
+                char::from(*byte),
+                index
+            ),
+            InvalidPercentEncoding { index } => write!(
+                formatter,
+                "invalid fragment percent encoding at index {}",
+                index
+            ),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for FragmentError {}
 
 impl From<Infallible> for FragmentError {
-    fn from(_: Infallible) -> Self {
-        FragmentError::InvalidCharacter
+    fn from(value: Infallible) -> Self {
+        match value {}
     }
 }
 
 #[cfg(test)]
 mod test {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
     use super::*;
 
+    #[test]
+    fn test_fragment_decode() {
+        fn test_case(value: &str, expected: &str) {
+            let fragment = Fragment::try_from(value).unwrap();
+            assert_eq!(fragment.decode(), expected);
+        }
+
+        test_case("", "");
+        test_case("a%20b", "a b");
+        test_case("%41%42%43", "ABC");
+        test_case("%E2%9C%93", "\u{2713}");
+        test_case("caf%C3%A9!", "caf\u{e9}!");
+        test_case("%FF", "\u{fffd}");
+
+        let fragment = Fragment::try_from("fragment").unwrap();
+
+        match fragment.decode() {
+            Cow::Borrowed(decoded) => assert_eq!(decoded, "fragment"),
+            Cow::Owned(_) => panic!("expected borrowed decoded fragment"),
+        }
+    }
+
+    #[test]
+    fn test_fragment_empty() {
+        assert_eq!(Fragment::EMPTY.as_str(), "");
+        assert!(Fragment::EMPTY.is_empty());
+        assert!(Fragment::EMPTY.is_normalized());
+        assert_eq!(Fragment::EMPTY, Fragment::try_from("").unwrap());
+        assert!(!Fragment::try_from("fragment").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_fragment_from_str() {
+        use self::FragmentError::*;
+
+        assert_eq!("section-1".parse::<Fragment>().unwrap(), "section-1");
+        assert_eq!("fr%61gment".parse::<Fragment>().unwrap(), "fragment");
+        assert_eq!(
+            " ".parse::<Fragment>(),
+            Err(InvalidCharacter {
+                byte: b' ',
+                index: 0
+            })
+        );
+        assert_eq!(
+            "%zz".parse::<Fragment>(),
+            Err(InvalidPercentEncoding { index: 0 })
+        );
+    }
+
+    #[test]
+    fn test_fragment_from_unencoded() {
+        fn test_case(value: &str, expected: &str) {
+            let fragment = Fragment::from_unencoded(value);
+            assert_eq!(fragment.as_str(), expected);
+            assert!(fragment.is_normalized());
+            assert_eq!(Fragment::try_from(fragment.as_str()).unwrap(), fragment);
+            assert_eq!(fragment.decode(), value);
+        }
+
+        test_case("", "");
+        test_case("fragment", "fragment");
+        test_case("a b", "a%20b");
+        test_case("a#b", "a%23b");
+        test_case("100%", "100%25");
+        test_case("\u{2713}", "%E2%9C%93");
+        test_case("/?:@!$&'()*+,;=", "/?:@!$&'()*+,;=");
+    }
+
+    #[test]
+    fn test_fragment_new_unchecked() {
+        fn test_case(value: &str) {
+            // Safety: The test cases are all valid.
+            let mut fragment = unsafe { Fragment::new_unchecked(value) };
+            let mut expected = Fragment::try_from(value).unwrap();
+            assert_eq!(fragment, expected);
+            assert_eq!(fragment.as_str(), value);
+
+            if fragment.is_normalized() {
+                assert!(expected.is_normalized());
+            }
+
+            fragment.normalize();
+            expected.normalize();
+            assert_eq!(fragment.as_str(), expected.as_str());
+            assert!(fragment.is_normalized());
+        }
+
+        test_case("fragment");
+        test_case("a%20b");
+        test_case("%7e");
+        test_case("");
+    }
+
     #[test]
     fn test_fragment_normalize() {
         fn test_case(value: &str, expected: &str) {
             let mut fragment = Fragment::try_from(value).unwrap();
             fragment.normalize();
-            assert_eq!(fragment, expected);
+            assert!(fragment.is_normalized());
+            assert_eq!(fragment.as_str(), expected);
+
+            let fragment = Fragment::try_from(expected).unwrap();
+            assert!(fragment.is_normalized());
         }
 
         test_case("", "");
         test_case("%ff", "%FF");
         test_case("%41", "A");
+        test_case("a%41b%42%43c%7e", "aAbBCc~");
+        test_case("%41%ff%42%2f%43", "A%FFB%2FC");
+    }
+
+    #[test]
+    fn test_fragment_ord() {
+        use std::collections::BTreeSet;
+
+        let encoded = Fragment::try_from("fr%61gment").unwrap();
+        let decoded = Fragment::try_from("fragment").unwrap();
+        assert_eq!(encoded, decoded);
+        assert_eq!(encoded.cmp(&decoded), Ordering::Equal);
+
+        let mut fragments = [
+            Fragment::try_from("z").unwrap(),
+            Fragment::try_from("fragment").unwrap(),
+            Fragment::try_from("a").unwrap(),
+            Fragment::try_from("fr%61gment").unwrap(),
+            Fragment::try_from("%2F").unwrap(),
+        ];
+        fragments.sort();
+
+        let fragments: Vec<&str> = fragments.iter().map(Fragment::as_str).collect();
+        assert_eq!(fragments[0], "%2F");
+        assert_eq!(fragments[1], "a");
+        assert!(fragments[2..4].contains(&"fragment"));
+        assert!(fragments[2..4].contains(&"fr%61gment"));
+        assert_eq!(fragments[4], "z");
+
+        let set: BTreeSet<Fragment> = vec![encoded, decoded].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_fragment_owned() {
+        let value = String::from("fr%61gment");
+        let pointer = value.as_ptr();
+        let capacity = value.capacity();
+        let fragment = Fragment::try_from(value).unwrap();
+        assert_eq!(fragment.as_str().as_ptr(), pointer);
+        assert!(!fragment.is_normalized());
+        assert!(matches!(&fragment.fragment, Cow::Owned(owned) if owned.capacity() == capacity));
+
+        let mut value = Vec::with_capacity(32);
+        value.extend_from_slice(b"fragment");
+        let pointer = value.as_ptr();
+        let fragment = Fragment::try_from(value).unwrap();
+        assert_eq!(fragment.as_str().as_ptr(), pointer);
+        assert_eq!(fragment.as_str(), "fragment");
+        assert!(fragment.is_normalized());
+        assert!(matches!(&fragment.fragment, Cow::Owned(owned) if owned.capacity() == 32));
+
+        assert_eq!(
+            Fragment::try_from(String::from("a b")),
+            Err(FragmentError::InvalidCharacter {
+                byte: b' ',
+                index: 1
+            })
+        );
+        assert_eq!(
+            Fragment::try_from(vec![b'a', 0xFF]),
+            Err(FragmentError::InvalidCharacter {
+                byte: 0xFF,
+                index: 1
+            })
+        );
+        assert_eq!(
+            Fragment::try_from(b"%4".to_vec()),
+            Err(FragmentError::InvalidPercentEncoding { index: 0 })
+        );
     }
 
     #[test]
@@ -354,9 +765,67 @@ mod test {
         assert_eq!(Fragment::try_from("fRaGmEnT").unwrap(), "fRaGmEnT");
         assert_eq!(Fragment::try_from("%ff%ff%ff%41").unwrap(), "%ff%ff%ff%41");
 
-        assert_eq!(Fragment::try_from(" "), Err(InvalidCharacter));
-        assert_eq!(Fragment::try_from("%"), Err(InvalidPercentEncoding));
-        assert_eq!(Fragment::try_from("%f"), Err(InvalidPercentEncoding));
-        assert_eq!(Fragment::try_from("%zz"), Err(InvalidPercentEncoding));
+        assert_eq!(
+            Fragment::try_from(" "),
+            Err(InvalidCharacter {
+                byte: b' ',
+                index: 0
+            })
+        );
+        assert_eq!(
+            Fragment::try_from("ab#c"),
+            Err(InvalidCharacter {
+                byte: b'#',
+                index: 2
+            })
+        );
+        assert_eq!(
+            Fragment::try_from("%"),
+            Err(InvalidPercentEncoding { index: 0 })
+        );
+        assert_eq!(
+            Fragment::try_from("%f"),
+            Err(InvalidPercentEncoding { index: 0 })
+        );
+        assert_eq!(
+            Fragment::try_from("%zz"),
+            Err(InvalidPercentEncoding { index: 0 })
+        );
+        assert_eq!(
+            Fragment::try_from("a%41%g1"),
+            Err(InvalidPercentEncoding { index: 4 })
+        );
+    }
+
+    #[test]
+    fn test_fragment_error_display() {
+        let error = Fragment::try_from("ab c").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid fragment character ' ' at index 2"
+        );
+
+        let error = Fragment::try_from("ab%zz").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid fragment percent encoding at index 2"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_fragment_serde() {
+        let fragment = Fragment::try_from("fr%61gment").unwrap();
+        let serialized = serde_json::to_string(&fragment).unwrap();
+        assert_eq!(serialized, r#""fr%61gment""#);
+
+        let deserialized: Fragment = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.as_str(), "fr%61gment");
+        assert_eq!(deserialized, fragment);
+
+        let error = serde_json::from_str::<Fragment>(r#"" ""#).unwrap_err();
+        assert!(error.to_string().contains("invalid fragment character"));
+        assert!(serde_json::from_str::<Fragment>(r#""%zz""#).is_err());
+        assert!(serde_json::from_str::<Fragment>("1").is_err());
     }
 }
@@ -0,0 +1,115 @@
+//! Helpers shared by the [`arbitrary`](https://docs.rs/arbitrary) implementations and the
+//! [`proptest`](https://docs.rs/proptest) strategies.
+//!
+//! The allowed characters of each component are taken from the same character maps used by the
+//! parsers, so the generated values always pass validation.
+
+#[cfg(feature = "proptest")]
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::authority::{Authority, Host};
+use crate::fragment::Fragment;
+use crate::path::Path;
+use crate::query::Query;
+use crate::scheme::Scheme;
+use crate::uri::URI;
+
+/// The maximum length of generated strings, counting each percent-encoding as one.
+pub(crate) const MAX_LENGTH: usize = 16;
+
+/// The maximum number of generated path segments.
+pub(crate) const MAX_SEGMENTS: usize = 4;
+
+/// Returns the bytes allowed by the given character map, excluding `'%'` and the given excluded
+/// bytes.
+pub(crate) fn allowed_bytes(char_map: &[u8; 256], excluded: &[u8]) -> Vec<u8> {
+    (0..=255)
+        .filter(|&byte| char_map[byte as usize] != 0 && byte != b'%' && !excluded.contains(&byte))
+        .collect()
+}
+
+/// Builds an authority from its generated parts.
+pub(crate) fn authority(
+    user_info: Option<(String, Option<String>)>,
+    host: &Host<'_>,
+    port: Option<u16>,
+) -> Authority<'static> {
+    let mut authority = String::new();
+
+    if let Some((username, password)) = user_info {
+        authority.push_str(&username);
+
+        if let Some(password) = password {
+            authority.push(':');
+            authority.push_str(&password);
+        }
+
+        authority.push('@');
+    }
+
+    authority.push_str(&host.to_string());
+
+    if let Some(port) = port {
+        authority.push(':');
+        authority.push_str(&port.to_string());
+    }
+
+    Authority::try_from(authority.as_str())
+        .unwrap()
+        .into_owned()
+}
+
+/// Returns a regular expression matching strings of at most [`MAX_LENGTH`] bytes allowed by the
+/// given character map, excluding the given excluded bytes, and percent-encodings if the character
+/// map allows them.
+#[cfg(feature = "proptest")]
+pub(crate) fn component_regex(char_map: &[u8; 256], excluded: &[u8]) -> String {
+    let class: String = allowed_bytes(char_map, excluded)
+        .into_iter()
+        .map(|byte| format!("\\x{:02X}", byte))
+        .collect();
+
+    if char_map[b'%' as usize] != 0 {
+        format!("([{}]|%[0-9A-Fa-f]{{2}}){{0,{}}}", class, MAX_LENGTH)
+    } else {
+        format!("[{}]{{0,{}}}", class, MAX_LENGTH)
+    }
+}
+
+/// Builds a path from its generated segments.
+pub(crate) fn path(absolute: bool, segments: &[String]) -> Path<'static> {
+    let mut path = String::new();
+
+    if absolute {
+        path.push('/');
+    }
+
+    path.push_str(&segments.join("/"));
+    Path::try_from(path.as_str()).unwrap().into_owned()
+}
+
+/// Builds a URI from its generated components.
+///
+/// A path starting with `"//"` is only valid if there is an authority, so such a path is replaced
+/// with an empty one when there is not.
+pub(crate) fn uri(
+    scheme: Scheme<'static>,
+    authority: Option<Authority<'static>>,
+    path: Path<'static>,
+    query: Option<Query<'static>>,
+    fragment: Option<Fragment<'static>>,
+) -> URI<'static> {
+    match URI::from_parts(
+        scheme.clone(),
+        authority.clone(),
+        path,
+        query.clone(),
+        fragment.clone(),
+    ) {
+        Ok(uri) => uri,
+        Err(_) => URI::from_parts(scheme, authority, "", query, fragment).unwrap(),
+    }
+}
@@ -1,24 +1,56 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod generate;
 mod utility;
 
 pub mod authority;
+pub mod data_uri;
 pub mod fragment;
+pub mod mailto_uri;
 pub mod path;
 pub mod query;
 pub mod relative_reference;
 pub mod scheme;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 pub mod uri;
 pub mod uri_reference;
 
+#[cfg(feature = "idna")]
+pub use self::authority::IDNAError;
 pub use self::authority::{
-    Authority, AuthorityError, Host, HostError, Password, PasswordError, PortError, RegisteredName,
-    RegisteredNameError, Username, UsernameError,
+    Authority, AuthorityError, AuthorityPolicy, Host, HostError, IPvFuture, Password,
+    PasswordError, PortError, RegisteredName, RegisteredNameError, UserInfo, UserInfoError,
+    Username, UsernameError, ZoneIdentifier, ZoneIdentifierError,
 };
+pub use self::data_uri::{DataURI, DataURIError};
 pub use self::fragment::{Fragment, FragmentError};
-pub use self::path::{Path, PathError, Segment};
-pub use self::query::{Query, QueryError};
+pub use self::mailto_uri::{MailToURI, MailToURIError};
+pub use self::path::{Path, PathError, Segment, SegmentParameters};
+pub use self::query::{Query, QueryBuilder, QueryError, QueryPairs};
 pub use self::relative_reference::{
-    RelativeReference, RelativeReferenceBuilder, RelativeReferenceError,
+    RelativeReference, RelativeReferenceBuilder, RelativeReferenceError, RelativeReferenceErrorKind,
 };
 pub use self::scheme::{Scheme, SchemeError, SchemeStatus, UnregisteredScheme};
-pub use self::uri::{URIBuilder, URIError, URI};
-pub use self::uri_reference::{URIReference, URIReferenceBuilder, URIReferenceError};
+#[cfg(feature = "std")]
+pub use self::uri::FilePathError;
+#[cfg(feature = "http")]
+pub use self::uri::HTTPConversionError;
+#[cfg(feature = "url")]
+pub use self::uri::URLConversionError;
+pub use self::uri::{
+    normalize, parse_uri_prefix, Origin, ParseOptions, URIBuilder, URIError, URIErrorKind,
+    URISpans, URIValidationError, URI,
+};
+pub use self::uri_reference::{
+    Component, Components, PathMut, URIReference, URIReferenceBuilder, URIReferenceError,
+    URIReferenceErrorKind,
+};
+pub use self::utility::{percent_decode, percent_encode, InvalidPercentEncoding};
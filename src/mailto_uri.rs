@@ -0,0 +1,260 @@
+//! Mailto URIs
+//!
+//! See [RFC6068](https://tools.ietf.org/html/rfc6068).
+//!
+//! A mailto URI designates email recipients and header fields, such as in
+//! `"mailto:a@example.com,b@example.com?subject=Hi"`. Since the recipients are just the path and the
+//! header fields are just the query of the URI as far as
+//! [RFC3986](https://tools.ietf.org/html/rfc3986) is concerned, [`MailToURI`] can be used to get the
+//! percent-decoded recipients and header fields out of it.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! use uriparse::{MailToURI, URI};
+//!
+//! let uri = URI::try_from("mailto:a@example.com,b@example.com?subject=Hello%20World").unwrap();
+//! let mailto_uri = MailToURI::try_from(&uri).unwrap();
+//! assert_eq!(mailto_uri.to(), ["a@example.com", "b@example.com"]);
+//! assert_eq!(mailto_uri.header("subject"), Some("Hello World"));
+//! ```
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::query::{raw_pairs, split_pair};
+use crate::scheme::Scheme;
+use crate::uri::URI;
+use crate::utility::decode_percent_encoded;
+
+/// A mailto URI as defined in [RFC6068](https://tools.ietf.org/html/rfc6068), with its recipients
+/// and header fields percent-decoded.
+///
+/// This is created from a [`URI`] with the `mailto` scheme using [`MailToURI::try_from`] or
+/// [`URI::as_mailto_uri`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct MailToURI {
+    /// The percent-decoded header fields, in the order they appear in the query.
+    headers: Vec<(String, String)>,
+
+    /// The percent-decoded recipient addresses, from both the path and the `to` header fields.
+    to: Vec<String>,
+}
+
+impl MailToURI {
+    /// Returns the value of the first header field with the given name, if present.
+    ///
+    /// Header field names are case-insensitive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{MailToURI, URI};
+    ///
+    /// let uri = URI::try_from("mailto:a@example.com?Subject=Hi&body=Hello%0D%0AWorld").unwrap();
+    /// let mailto_uri = MailToURI::try_from(&uri).unwrap();
+    /// assert_eq!(mailto_uri.header("subject"), Some("Hi"));
+    /// assert_eq!(mailto_uri.header("body"), Some("Hello\r\nWorld"));
+    /// assert_eq!(mailto_uri.header("cc"), None);
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the percent-decoded header fields of the mailto URI (e.g. `subject`, `cc`, `bcc`,
+    /// and `body`), in the order they appear in the query.
+    ///
+    /// This includes any `to` header fields, even though their addresses are also part of
+    /// [`MailToURI::to`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{MailToURI, URI};
+    ///
+    /// let uri = URI::try_from("mailto:?cc=b@example.com&subject=Hi").unwrap();
+    /// let mailto_uri = MailToURI::try_from(&uri).unwrap();
+    /// assert_eq!(
+    ///     mailto_uri.headers(),
+    ///     [
+    ///         ("cc".to_string(), "b@example.com".to_string()),
+    ///         ("subject".to_string(), "Hi".to_string())
+    ///     ]
+    /// );
+    /// ```
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Returns the percent-decoded recipient addresses of the mailto URI.
+    ///
+    /// The addresses in the path come first, followed by those in any `to` header fields. Multiple
+    /// addresses are separated by `','`, and empty addresses are skipped, so the list may be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{MailToURI, URI};
+    ///
+    /// let uri = URI::try_from("mailto:a@example.com?to=b@example.com,c@example.com").unwrap();
+    /// assert_eq!(
+    ///     MailToURI::try_from(&uri).unwrap().to(),
+    ///     ["a@example.com", "b@example.com", "c@example.com"]
+    /// );
+    ///
+    /// let uri = URI::try_from("mailto:?subject=Hi").unwrap();
+    /// assert!(MailToURI::try_from(&uri).unwrap().to().is_empty());
+    /// ```
+    pub fn to(&self) -> &[String] {
+        &self.to
+    }
+}
+
+impl<'uri> TryFrom<&URI<'uri>> for MailToURI {
+    type Error = MailToURIError;
+
+    fn try_from(value: &URI<'uri>) -> Result<Self, Self::Error> {
+        if value.scheme() != &Scheme::MailTo {
+            return Err(MailToURIError::NotMailToURI);
+        }
+
+        let mut to: Vec<String> = value
+            .path()
+            .to_string()
+            .split(',')
+            .filter(|address| !address.is_empty())
+            .map(|address| decode_percent_encoded(address).into_owned())
+            .collect();
+        let mut headers = Vec::new();
+
+        if let Some(query) = value.query() {
+            // The raw pairs are used rather than `Query::pairs` so that the addresses in `to` header
+            // fields can be split on literal commas before being decoded.
+            for pair in raw_pairs(query.as_str()) {
+                let (key, value) = split_pair(pair);
+                let key = decode_percent_encoded(key);
+
+                if key.eq_ignore_ascii_case("to") {
+                    to.extend(
+                        value
+                            .split(',')
+                            .filter(|address| !address.is_empty())
+                            .map(|address| decode_percent_encoded(address).into_owned()),
+                    );
+                }
+
+                headers.push((key.into_owned(), decode_percent_encoded(value).into_owned()));
+            }
+        }
+
+        Ok(MailToURI { headers, to })
+    }
+}
+
+/// An error representing a URI that is not a valid mailto URI.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MailToURIError {
+    /// The URI does not have the `mailto` scheme.
+    NotMailToURI,
+}
+
+impl Display for MailToURIError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        use self::MailToURIError::*;
+
+        match self {
+            NotMailToURI => write!(formatter, "not mailto URI"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for MailToURIError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mailto_uri() {
+        fn test_case(value: &str, to: &[&str], headers: &[(&str, &str)]) {
+            let uri = URI::try_from(value).unwrap();
+            let mailto_uri = MailToURI::try_from(&uri).unwrap();
+            assert_eq!(mailto_uri.to(), to);
+
+            let actual: Vec<(&str, &str)> = mailto_uri
+                .headers()
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect();
+            assert_eq!(actual, headers);
+        }
+
+        test_case("mailto:a@example.com", &["a@example.com"], &[]);
+        test_case(
+            "mailto:a@example.com,b@example.com",
+            &["a@example.com", "b@example.com"],
+            &[],
+        );
+        test_case(
+            "mailto:a@example.com?subject=Hello%20World",
+            &["a@example.com"],
+            &[("subject", "Hello World")],
+        );
+        test_case(
+            "mailto:a@example.com?subject=a+b",
+            &["a@example.com"],
+            &[("subject", "a+b")],
+        );
+        test_case(
+            "mailto:?to=a@example.com&cc=b@example.com&bcc=c@example.com&body=Hi%21",
+            &["a@example.com"],
+            &[
+                ("to", "a@example.com"),
+                ("cc", "b@example.com"),
+                ("bcc", "c@example.com"),
+                ("body", "Hi!"),
+            ],
+        );
+        test_case(
+            "mailto:a@example.com?To=b@example.com,c@example.com",
+            &["a@example.com", "b@example.com", "c@example.com"],
+            &[("To", "b@example.com,c@example.com")],
+        );
+        test_case(
+            "mailto:%22not%2Ca%22@example.com,b@example.com",
+            &["\"not,a\"@example.com", "b@example.com"],
+            &[],
+        );
+        test_case(
+            "mailto:?to=%22not%2Ca%22@example.com",
+            &["\"not,a\"@example.com"],
+            &[("to", "\"not,a\"@example.com")],
+        );
+        test_case("mailto:", &[], &[]);
+        test_case("mailto:,", &[], &[]);
+        test_case("MAILTO:a@example.com", &["a@example.com"], &[]);
+    }
+
+    #[test]
+    fn test_mailto_uri_error() {
+        let uri = URI::try_from("http://example.com").unwrap();
+        assert_eq!(MailToURI::try_from(&uri), Err(MailToURIError::NotMailToURI));
+    }
+}
@@ -2,22 +2,27 @@
 //!
 //! See [[RFC3986, Section 3.3](https://tools.ietf.org/html/rfc3986#section-3.3)].
 
-use std::borrow::Cow;
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter, Write};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter, Write};
-use std::hash::{Hash, Hasher};
-use std::ops::Deref;
-use std::str;
 
 use crate::utility::{
-    get_percent_encoded_value, normalize_string, percent_encoded_equality, percent_encoded_hash,
+    decode_percent_encoded, encode_percent_encoded, get_percent_encoded_value, normalize_string,
+    percent_decode, percent_encoded_cmp, percent_encoded_equality, percent_encoded_hash,
     UNRESERVED_CHAR_MAP,
 };
 
 /// A map of byte characters that determines if a character is a valid path character.
 #[rustfmt::skip]
-const PATH_CHAR_MAP: [u8; 256] = [
+pub(crate) const PATH_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -54,7 +59,7 @@ const PATH_CHAR_MAP: [u8; 256] = [
 /// mean that either the path or a given segment is normalized. If the path or a segment needs to be
 /// normalized, use either the [`Path::normalize`] or [`Segment::normalize`] functions,
 /// respectively.
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Path<'path> {
     /// whether the path is absolute. Specifically, a path is absolute if it starts with a
     /// `'/'`.
@@ -77,6 +82,20 @@ pub struct Path<'path> {
 }
 
 impl<'path> Path<'path> {
+    /// Returns a new path which is identical but has a lifetime tied to this path.
+    ///
+    /// The segments borrow from this path, so only the list of segments is allocated.
+    pub fn as_borrowed(&self) -> Path<'_> {
+        Path {
+            absolute: self.absolute,
+            double_dot_segment_count: self.double_dot_segment_count,
+            leading_double_dot_segment_count: self.leading_double_dot_segment_count,
+            segments: self.segments.iter().map(Segment::as_borrowed).collect(),
+            single_dot_segment_count: self.single_dot_segment_count,
+            unnormalized_count: self.unnormalized_count,
+        }
+    }
+
     /// Clears all segments from the path leaving a single empty segment.
     ///
     /// # Examples
@@ -96,6 +115,77 @@ impl<'path> Path<'path> {
         self.segments.push(Segment::empty());
     }
 
+    /// Returns an iterator over the segments of the path with all percent-encoded bytes decoded.
+    ///
+    /// See [`Segment::decode`] for how each segment is decoded.
+    ///
+    /// The leading `'/'` of an absolute path is not a segment, so no leading empty string is
+    /// yielded for it. However, a trailing `'/'` results in a trailing empty segment, which is
+    /// yielded. This means that `"/a/b"` and `"a/b"` both yield `"a"` then `"b"`, while `"/a/b/"`
+    /// yields `"a"`, `"b"`, then `""`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let path = Path::try_from("/my%20dir/file").unwrap();
+    /// let segments: Vec<_> = path.decoded_segments().collect();
+    /// assert_eq!(segments, ["my dir", "file"]);
+    /// ```
+    pub fn decoded_segments(&self) -> impl Iterator<Item = Cow<'_, str>> + '_ {
+        self.segments.iter().map(Segment::decode)
+    }
+
+    /// Makes the path end with a `'/'` by adding a trailing empty segment if there is not one
+    /// already.
+    ///
+    /// An empty relative path is left unchanged, since adding a `'/'` would make it absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path").unwrap();
+    /// path.ensure_directory();
+    /// assert_eq!(path, "/my/path/");
+    /// path.ensure_directory();
+    /// assert_eq!(path, "/my/path/");
+    /// ```
+    pub fn ensure_directory(&mut self) {
+        if !self.segments.last().unwrap().is_empty() {
+            self.segments.push(Segment::empty());
+        }
+    }
+
+    /// Makes the path not end with a `'/'` by removing the trailing empty segment if there is one.
+    ///
+    /// The root path `"/"` is left unchanged, since removing its `'/'` would make it relative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut path = Path::try_from("/my/path/").unwrap();
+    /// path.ensure_file();
+    /// assert_eq!(path, "/my/path");
+    /// path.ensure_file();
+    /// assert_eq!(path, "/my/path");
+    /// ```
+    pub fn ensure_file(&mut self) {
+        if self.is_directory() && self.segments.len() > 1 {
+            self.segments.pop();
+        }
+    }
+
     /// Converts the [`Path`] into an owned copy.
     ///
     /// If you construct the path from a source with a non-static lifetime, you may run into
@@ -139,6 +229,26 @@ impl<'path> Path<'path> {
         self.absolute
     }
 
+    /// Returns whether the path ends with a `'/'`, i.e. whether the last segment is empty.
+    ///
+    /// The root path `"/"` is a directory, while the empty relative path `""` is not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// assert!(Path::try_from("/my/path/").unwrap().is_directory());
+    /// assert!(!Path::try_from("/my/path").unwrap().is_directory());
+    /// assert!(Path::try_from("/").unwrap().is_directory());
+    /// ```
+    pub fn is_directory(&self) -> bool {
+        let last_empty = self.segments.last().unwrap().is_empty();
+        last_empty && (self.absolute || self.segments.len() > 1)
+    }
+
     /// Returns whether the path is normalized either as or as not a reference.
     ///
     /// See [`Path::normalize`] for a full description of what path normalization entails.
@@ -308,9 +418,50 @@ impl<'path> Path<'path> {
         self.segments.truncate(new_length);
     }
 
-    /// Pops the last segment off of the path.
+    /// Joins the given path onto this path and returns the result.
+    ///
+    /// If `other` is absolute, it replaces this path entirely. Otherwise, the last segment of this
+    /// path is replaced by the segments of `other`, so `"/a/b"` joined with `"c"` is `"/a/c"` while
+    /// `"/a/b/"` joined with `"c"` is `"/a/b/c"`. This is the merge described in
+    /// [[RFC3986, Section 5.2.3](https://tools.ietf.org/html/rfc3986#section-5.2.3)]. Dot segments
+    /// are then removed from the result as if by [`Path::remove_dot_segments`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let base = Path::try_from("/my/path").unwrap();
+    /// let other = Path::try_from("sub/dir").unwrap();
+    /// assert_eq!(base.join(&other), "/my/sub/dir");
+    ///
+    /// let other = Path::try_from("../dir").unwrap();
+    /// assert_eq!(base.join(&other), "/dir");
+    /// ```
+    pub fn join(&self, other: &Path<'path>) -> Path<'path> {
+        let mut path = if other.absolute {
+            other.clone()
+        } else {
+            let mut path = self.clone();
+            path.pop();
+
+            for segment in other.segments() {
+                path.push(segment.clone()).unwrap();
+            }
+
+            path
+        };
+
+        path.remove_dot_segments();
+        path
+    }
+
+    /// Pops the last segment off of the path and returns it.
     ///
-    /// If the path only contains one segment, then that segment will become empty.
+    /// If the path only contains one segment, then that segment will become empty. If that segment
+    /// was already empty, nothing is removed and [`None`] is returned.
     ///
     /// ```
     /// use std::convert::TryFrom;
@@ -318,22 +469,25 @@ impl<'path> Path<'path> {
     /// use uriparse::Path;
     ///
     /// let mut path = Path::try_from("/my/path").unwrap();
-    /// path.pop();
+    /// assert_eq!(path.pop().unwrap(), "path");
     /// assert_eq!(path, "/my");
-    /// path.pop();
+    /// assert_eq!(path.pop().unwrap(), "my");
     /// assert_eq!(path, "/");
+    /// assert_eq!(path.pop(), None);
     /// ```
-    pub fn pop(&mut self) {
+    pub fn pop(&mut self) -> Option<Segment<'path>> {
+        if self.segments.len() == 1 && self.segments[0].is_empty() {
+            return None;
+        }
+
         let segment = self.segments.pop().unwrap();
 
         if segment.is_single_dot_segment() {
-            self.single_dot_segment_count =
-                self.single_dot_segment_count.checked_sub(1).unwrap_or(0);
+            self.single_dot_segment_count = self.single_dot_segment_count.saturating_sub(1);
         }
 
         if segment.is_double_dot_segment() {
-            self.double_dot_segment_count =
-                self.double_dot_segment_count.checked_sub(1).unwrap_or(0);
+            self.double_dot_segment_count = self.double_dot_segment_count.saturating_sub(1);
 
             if self.double_dot_segment_count < self.leading_double_dot_segment_count {
                 self.leading_double_dot_segment_count -= 1;
@@ -341,12 +495,14 @@ impl<'path> Path<'path> {
         }
 
         if !segment.is_normalized() {
-            self.unnormalized_count = self.unnormalized_count.checked_sub(1).unwrap_or(0);
+            self.unnormalized_count = self.unnormalized_count.saturating_sub(1);
         }
 
         if self.segments.is_empty() {
             self.segments.push(Segment::empty());
         }
+
+        Some(segment)
     }
 
     /// Pushes a segment onto the path.
@@ -379,18 +535,20 @@ impl<'path> Path<'path> {
         Segment<'path>: TryFrom<TSegment, Error = TSegmentError>,
         PathError: From<TSegmentError>,
     {
-        if self.segments.len() as u16 == u16::max_value() {
+        if self.segments.len() as u16 == u16::MAX {
             return Err(PathError::ExceededMaximumLength);
         }
 
         let segment = Segment::try_from(segment)?;
+        let replace_empty =
+            !segment.is_empty() && self.segments.len() == 1 && self.segments[0].is_empty();
 
         if segment.is_single_dot_segment() {
             self.single_dot_segment_count += 1;
         }
 
         if segment.is_double_dot_segment() {
-            if self.segments.len() as u16 == self.double_dot_segment_count {
+            if replace_empty || self.segments.len() as u16 == self.double_dot_segment_count {
                 self.leading_double_dot_segment_count += 1;
             }
 
@@ -401,7 +559,7 @@ impl<'path> Path<'path> {
             self.unnormalized_count += 1;
         }
 
-        if segment != "" && self.segments.len() == 1 && self.segments[0].as_str().is_empty() {
+        if replace_empty {
             self.segments[0] = segment;
         } else {
             self.segments.push(segment);
@@ -472,11 +630,7 @@ impl<'path> Path<'path> {
 
             new_length += 1;
 
-            if i < self.segments.len() - 1 {
-                input_absolute = true;
-            } else {
-                input_absolute = false;
-            }
+            input_absolute = i < self.segments.len() - 1;
         }
 
         if input_absolute {
@@ -497,6 +651,14 @@ impl<'path> Path<'path> {
         self.leading_double_dot_segment_count = 0;
         self.single_dot_segment_count = 0;
         self.segments.truncate(new_length);
+
+        if !normalize_segments {
+            self.unnormalized_count = self
+                .segments
+                .iter()
+                .filter(|segment| !segment.is_normalized())
+                .count() as u16;
+        }
     }
 
     /// Returns the segments of the path.
@@ -520,7 +682,8 @@ impl<'path> Path<'path> {
     /// Returns the segments of the path mutably.
     ///
     /// Due to the required restriction that there must be at least one segment in a path, this
-    /// mutability only applies to the segments themselves, not the container.
+    /// mutability only applies to the segments themselves, not the container. Use [`Path::push`]
+    /// and [`Path::pop`] to add or remove segments.
     ///
     /// # Examples
     ///
@@ -559,7 +722,7 @@ impl<'path> Path<'path> {
     /// Returns a new path which is identical but has a lifetime tied to this path.
     ///
     /// This function will perform a memory allocation.
-    pub fn to_borrowed(&self) -> Path {
+    pub fn to_borrowed(&self) -> Path<'_> {
         let segments = self.segments.iter().map(Segment::as_borrowed).collect();
 
         Path {
@@ -571,6 +734,27 @@ impl<'path> Path<'path> {
             unnormalized_count: self.unnormalized_count,
         }
     }
+
+    /// Appends the path to the given string.
+    ///
+    /// The path is formatted with [`Display`] directly into the string, so no intermediate string
+    /// is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Path;
+    ///
+    /// let mut string = String::from("http://example.com");
+    /// Path::try_from("/my/path").unwrap().write_to(&mut string);
+    /// assert_eq!(string, "http://example.com/my/path");
+    /// ```
+    pub fn write_to(&self, out: &mut String) {
+        // Writing to a string cannot fail.
+        write!(out, "{}", self).unwrap();
+    }
 }
 
 impl Display for Path<'_> {
@@ -591,16 +775,42 @@ impl Display for Path<'_> {
     }
 }
 
+impl Eq for Path<'_> {}
+
 impl<'path> From<Path<'path>> for String {
     fn from(value: Path<'path>) -> Self {
-        value.to_string()
+        let mut string = String::new();
+        value.write_to(&mut string);
+        string
+    }
+}
+
+impl Hash for Path<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.absolute.hash(state);
+        self.segments.hash(state);
+    }
+}
+
+impl Ord for Path<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.absolute, &self.segments).cmp(&(other.absolute, &other.segments))
+    }
+}
+
+impl PartialEq for Path<'_> {
+    fn eq(&self, other: &Path) -> bool {
+        self.absolute == other.absolute && self.segments == other.segments
     }
 }
 
 impl PartialEq<[u8]> for Path<'_> {
     fn eq(&self, mut other: &[u8]) -> bool {
         if self.absolute {
-            match other.get(0) {
+            match other.first() {
                 Some(&byte) => {
                     if byte != b'/' {
                         return false;
@@ -622,7 +832,7 @@ impl PartialEq<[u8]> for Path<'_> {
             other = &other[len..];
 
             if index < self.segments.len() - 1 {
-                match other.get(0) {
+                match other.first() {
                     Some(&byte) => {
                         if byte != b'/' {
                             return false;
@@ -641,7 +851,7 @@ impl PartialEq<[u8]> for Path<'_> {
 
 impl<'path> PartialEq<Path<'path>> for [u8] {
     fn eq(&self, other: &Path<'path>) -> bool {
-        self == other
+        other == self
     }
 }
 
@@ -651,9 +861,9 @@ impl<'a> PartialEq<&'a [u8]> for Path<'_> {
     }
 }
 
-impl<'a, 'path> PartialEq<Path<'path>> for &'a [u8] {
+impl<'path> PartialEq<Path<'path>> for &[u8] {
     fn eq(&self, other: &Path<'path>) -> bool {
-        self == other
+        other == *self
     }
 }
 
@@ -675,17 +885,23 @@ impl<'a> PartialEq<&'a str> for Path<'_> {
     }
 }
 
-impl<'a, 'path> PartialEq<Path<'path>> for &'a str {
+impl<'path> PartialEq<Path<'path>> for &str {
     fn eq(&self, other: &Path<'path>) -> bool {
         self.as_bytes() == other
     }
 }
 
+impl PartialOrd for Path<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'path> TryFrom<&'path [u8]> for Path<'path> {
     type Error = PathError;
 
     fn try_from(value: &'path [u8]) -> Result<Self, Self::Error> {
-        let (path, rest) = parse_path(value)?;
+        let (path, rest) = parse_path(value).map_err(|(error, _)| error)?;
 
         if rest.is_empty() {
             Ok(path)
@@ -706,6 +922,11 @@ impl<'path> TryFrom<&'path str> for Path<'path> {
 /// A segment of a path.
 ///
 /// Segments are separated from other segments with the `'/'` delimiter.
+///
+/// Like the other components, percent-encoding of unreserved characters plays no role in equality
+/// checking and hashing. Because of this, the segment does not implement `Borrow<str>`, as that
+/// would require `"segment"` and `"s%65gment"` to hash the same as their exact strings do. Use
+/// [`Segment::as_str`] to look up a segment in a map with string keys.
 #[derive(Clone, Debug)]
 pub struct Segment<'segment> {
     /// Whether the segment is normalized.
@@ -717,7 +938,7 @@ pub struct Segment<'segment> {
 
 impl Segment<'_> {
     /// Returns a new segment which is identical but has as lifetime tied to this segment.
-    pub fn as_borrowed(&self) -> Segment {
+    pub fn as_borrowed(&self) -> Segment<'_> {
         use self::Cow::*;
 
         let segment = match &self.segment {
@@ -747,6 +968,46 @@ impl Segment<'_> {
         &self.segment
     }
 
+    /// Returns the segment with all percent-encoded bytes decoded.
+    ///
+    /// If the segment contains no percent-encodings, the returned value will borrow from the
+    /// segment. Decoded byte sequences that are not valid UTF-8 are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("my%2Fdir").unwrap();
+    /// assert_eq!(segment.decode(), "my/dir");
+    /// ```
+    pub fn decode(&self) -> Cow<'_, str> {
+        decode_percent_encoded(&self.segment)
+    }
+
+    /// Returns the segment with all percent-encoded bytes decoded, without requiring the decoded
+    /// bytes to be valid UTF-8.
+    ///
+    /// If the segment contains no percent-encodings, the returned value will borrow from the
+    /// segment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("caf%E9").unwrap();
+    /// assert_eq!(segment.decode_bytes(), &b"caf\xE9"[..]);
+    /// ```
+    pub fn decode_bytes(&self) -> Cow<'_, [u8]> {
+        percent_decode(self.segment.as_bytes()).unwrap()
+    }
+
     /// Constructs a segment that is empty.
     ///
     /// # Examples
@@ -763,6 +1024,34 @@ impl Segment<'_> {
         }
     }
 
+    /// Constructs a segment from arbitrary text by percent-encoding all bytes that are not valid
+    /// segment characters, including `'/'` and the `'%'` character itself.
+    ///
+    /// This is the inverse of [`Segment::decode`] and always succeeds. The resulting segment will
+    /// be normalized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, Segment};
+    ///
+    /// let segment = Segment::from_unencoded("my dir/a");
+    /// assert_eq!(segment.as_str(), "my%20dir%2Fa");
+    /// assert_eq!(segment.decode(), "my dir/a");
+    ///
+    /// let mut path = Path::try_from("/").unwrap();
+    /// path.push(segment).unwrap();
+    /// assert_eq!(path, "/my%20dir%2Fa");
+    /// ```
+    pub fn from_unencoded(value: &str) -> Segment<'static> {
+        Segment {
+            normalized: true,
+            segment: Cow::Owned(encode_percent_encoded(value, &PATH_CHAR_MAP)),
+        }
+    }
+
     /// Converts the [`Segment`] into an owned copy.
     ///
     /// If you construct the segment from a source with a non-static lifetime, you may run into
@@ -868,6 +1157,38 @@ impl Segment<'_> {
         self == "."
     }
 
+    /// Constructs a segment from the given string without checking that it is valid.
+    ///
+    /// This skips the validation done by [`Segment::try_from`], which is useful when the value is
+    /// already known to be valid (e.g. because it was taken from another segment). If the value
+    /// contains percent-encodings, the segment will not be considered normalized until
+    /// [`Segment::normalize`] is called, even if it already is.
+    ///
+    /// # Safety
+    ///
+    /// The value must be a valid segment, that is it must only consist of characters allowed in a
+    /// path segment as defined in
+    /// [[RFC3986, Section 3.3](https://tools.ietf.org/html/rfc3986#section-3.3)], which excludes
+    /// `'/'`, and every `'%'` must be followed by two hexadecimal digits. Other functions of this
+    /// crate, such as [`Segment::normalize`], rely on this to operate on the bytes of the segment
+    /// in place. Passing an invalid value is undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Segment;
+    ///
+    /// // Safety: The value is a valid segment.
+    /// let segment = unsafe { Segment::new_unchecked("my%20segment") };
+    /// assert_eq!(segment.as_str(), "my%20segment");
+    /// ```
+    pub unsafe fn new_unchecked(value: &str) -> Segment<'_> {
+        Segment {
+            normalized: !value.contains('%'),
+            segment: Cow::Borrowed(value),
+        }
+    }
+
     /// Normalizes the segment such that it will have no bytes that are in the unreserved character
     /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
@@ -894,10 +1215,45 @@ impl Segment<'_> {
     pub fn normalize(&mut self) {
         if !self.normalized {
             // Unsafe: Paths must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.segment.to_mut(), true) };
+            unsafe { normalize_string(self.segment.to_mut(), true) };
             self.normalized = true;
         }
     }
+
+    /// Returns an iterator over the percent-decoded parameters of the segment.
+    ///
+    /// Parameters are delimited by `';'`, as in matrix URIs (e.g. `"/a;x=1;y=2/b"`). The part
+    /// before the first `';'` is the name of the segment and is not yielded. Each parameter is
+    /// split on the first `'='`, and a parameter without a `'='` will have no value. Empty
+    /// parameters are skipped. The segment itself is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Segment;
+    ///
+    /// let segment = Segment::try_from("a;x=1;y;z=%3B").unwrap();
+    /// let parameters: Vec<_> = segment.parameters().collect();
+    /// assert_eq!(
+    ///     parameters,
+    ///     vec![
+    ///         ("x".into(), Some("1".into())),
+    ///         ("y".into(), None),
+    ///         ("z".into(), Some(";".into()))
+    ///     ]
+    /// );
+    /// assert_eq!(segment, "a;x=1;y;z=%3B");
+    /// ```
+    pub fn parameters(&self) -> SegmentParameters<'_> {
+        let parameters = match self.segment.find(';') {
+            Some(index) => &self.segment[index + 1..],
+            None => "",
+        };
+
+        SegmentParameters { parameters }
+    }
 }
 
 impl AsRef<[u8]> for Segment<'_> {
@@ -943,6 +1299,12 @@ impl Hash for Segment<'_> {
     }
 }
 
+impl Ord for Segment<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.segment.as_bytes(), other.segment.as_bytes(), true)
+    }
+}
+
 impl PartialEq for Segment<'_> {
     fn eq(&self, other: &Segment) -> bool {
         percent_encoded_equality(self.segment.as_bytes(), other.segment.as_bytes(), true)
@@ -967,7 +1329,7 @@ impl<'a> PartialEq<&'a [u8]> for Segment<'_> {
     }
 }
 
-impl<'a, 'segment> PartialEq<Segment<'segment>> for &'a [u8] {
+impl<'segment> PartialEq<Segment<'segment>> for &[u8] {
     fn eq(&self, other: &Segment<'segment>) -> bool {
         percent_encoded_equality(self, other.segment.as_bytes(), true)
     }
@@ -991,12 +1353,18 @@ impl<'a> PartialEq<&'a str> for Segment<'_> {
     }
 }
 
-impl<'a, 'segment> PartialEq<Segment<'segment>> for &'a str {
+impl<'segment> PartialEq<Segment<'segment>> for &str {
     fn eq(&self, other: &Segment<'segment>) -> bool {
         percent_encoded_equality(self.as_bytes(), other.segment.as_bytes(), true)
     }
 }
 
+impl PartialOrd for Segment<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'segment> TryFrom<&'segment [u8]> for Segment<'segment> {
     type Error = PathError;
 
@@ -1038,6 +1406,43 @@ impl<'segment> TryFrom<&'segment str> for Segment<'segment> {
     }
 }
 
+/// An iterator over the percent-decoded parameters of a segment.
+///
+/// This is created by [`Segment::parameters`].
+#[derive(Clone, Debug)]
+pub struct SegmentParameters<'segment> {
+    /// The remaining parameters of the segment that have yet to be iterated over.
+    parameters: &'segment str,
+}
+
+impl<'segment> Iterator for SegmentParameters<'segment> {
+    type Item = (Cow<'segment, str>, Option<Cow<'segment, str>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.parameters.is_empty() {
+            let (parameter, rest) = match self.parameters.find(';') {
+                Some(index) => (&self.parameters[..index], &self.parameters[index + 1..]),
+                None => (self.parameters, ""),
+            };
+            self.parameters = rest;
+
+            if parameter.is_empty() {
+                continue;
+            }
+
+            return Some(match parameter.find('=') {
+                Some(index) => (
+                    decode_percent_encoded(&parameter[..index]),
+                    Some(decode_percent_encoded(&parameter[index + 1..])),
+                ),
+                None => (decode_percent_encoded(parameter), None),
+            });
+        }
+
+        None
+    }
+}
+
 /// An error representing an invalid path.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -1065,6 +1470,7 @@ impl Display for PathError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for PathError {}
 
 impl From<Infallible> for PathError {
@@ -1074,7 +1480,9 @@ impl From<Infallible> for PathError {
 }
 
 /// Parses the path from the given byte string.
-pub(crate) fn parse_path(value: &[u8]) -> Result<(Path, &[u8]), PathError> {
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+pub(crate) fn parse_path(value: &[u8]) -> Result<(Path<'_>, &[u8]), (PathError, usize)> {
     struct SegmentInfo {
         absolute: bool,
         double_dot_segment_count: u16,
@@ -1134,6 +1542,7 @@ pub(crate) fn parse_path(value: &[u8]) -> Result<(Path, &[u8]), PathError> {
     } else {
         (value, false)
     };
+    let error = |error, index: usize| (error, index + usize::from(absolute));
 
     let mut bytes = value.iter();
     let mut segment_info = SegmentInfo {
@@ -1171,13 +1580,12 @@ pub(crate) fn parse_path(value: &[u8]) -> Result<(Path, &[u8]), PathError> {
                 segments.push(segment);
                 segment_end_index += 1;
                 segment_start_index = segment_end_index;
-                segment_info.index = segment_info
-                    .index
-                    .checked_add(1)
-                    .ok_or(PathError::ExceededMaximumLength)?;
+                segment_info.index = segment_info.index.checked_add(1).ok_or_else(|| {
+                    error(PathError::ExceededMaximumLength, segment_end_index - 1)
+                })?;
                 segment_info.normalized = true;
             }
-            0 => return Err(PathError::InvalidCharacter),
+            0 => return Err(error(PathError::InvalidCharacter, segment_end_index)),
             b'%' => match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
                 Ok((hex_value, uppercase)) => {
                     if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
@@ -1186,7 +1594,7 @@ pub(crate) fn parse_path(value: &[u8]) -> Result<(Path, &[u8]), PathError> {
 
                     segment_end_index += 3;
                 }
-                Err(_) => return Err(PathError::InvalidPercentEncoding),
+                Err(_) => return Err(error(PathError::InvalidPercentEncoding, segment_end_index)),
             },
             _ => segment_end_index += 1,
         }
@@ -1202,6 +1610,94 @@ pub(crate) fn parse_path(value: &[u8]) -> Result<(Path, &[u8]), PathError> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_path_decoded_segments() {
+        fn test_case(value: &str, expected: &[&str]) {
+            let path = Path::try_from(value).unwrap();
+            let segments: Vec<_> = path.decoded_segments().collect();
+            assert_eq!(segments, expected);
+        }
+
+        test_case("/a%20b/c", &["a b", "c"]);
+        test_case("a%20b/c", &["a b", "c"]);
+        test_case("/a/b/", &["a", "b", ""]);
+        test_case("/", &[""]);
+        test_case("", &[""]);
+        test_case("/a%2Fb", &["a/b"]);
+    }
+
+    #[test]
+    fn test_path_directory() {
+        fn test_case(value: &str, is_directory: bool, directory: &str, file: &str) {
+            let path = Path::try_from(value).unwrap();
+            assert_eq!(path.is_directory(), is_directory);
+
+            let mut directory_path = path.clone();
+            directory_path.ensure_directory();
+            assert_eq!(directory_path.to_string(), directory);
+            assert!(directory_path.is_directory() || value.is_empty());
+
+            let mut file_path = path;
+            file_path.ensure_file();
+            assert_eq!(file_path.to_string(), file);
+            assert!(!file_path.is_directory() || value == "/");
+        }
+
+        test_case("/a/", true, "/a/", "/a");
+        test_case("/a", false, "/a/", "/a");
+        test_case("/", true, "/", "/");
+        test_case("a/b/", true, "a/b/", "a/b");
+        test_case("a", false, "a/", "a");
+        test_case("", false, "", "");
+    }
+
+    #[test]
+    fn test_path_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+
+        fn hash(value: &str) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            Path::try_from(value).unwrap().hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(hash("/my/path"), hash("/my/path"));
+        assert_eq!(hash("/my/path"), hash("/%6Dy/p%61th"));
+        assert_eq!(hash("/my/%7e"), hash("/my/~"));
+        assert_eq!(hash("/a%2Fb"), hash("/a%2fb"));
+        assert_ne!(hash("/my/path"), hash("/my/other"));
+        assert_ne!(hash("/my/path"), hash("my/path"));
+        assert_ne!(hash("/a%2Fb"), hash("/a/b"));
+        assert_ne!(hash("/a/"), hash("/a"));
+
+        let mut set = HashSet::new();
+        set.insert(Path::try_from("/my/path").unwrap());
+        assert!(set.contains(&Path::try_from("/%6Dy/path").unwrap()));
+        assert!(!set.contains(&Path::try_from("/my/path/").unwrap()));
+    }
+
+    #[test]
+    fn test_path_join() {
+        fn test_case(base: &str, other: &str, expected: &str) {
+            let base = Path::try_from(base).unwrap();
+            let other = Path::try_from(other).unwrap();
+            assert_eq!(base.join(&other).to_string(), expected);
+        }
+
+        test_case("/a/b", "c", "/a/c");
+        test_case("/a/b/", "c", "/a/b/c");
+        test_case("/a/b", "c/d/", "/a/c/d/");
+        test_case("/a/b", "/c", "/c");
+        test_case("/a/b/", "/c/./d/..", "/c/");
+        test_case("/a", "c", "/c");
+        test_case("/", "c", "/c");
+        test_case("a/b", "c", "a/c");
+        test_case("", "c", "c");
+        test_case("/a/b/c", "../d", "/a/d");
+        test_case("/a/b/c", "../../../../d", "/d");
+    }
+
     #[test]
     fn test_path_normalize() {
         fn test_case(value: &str, expected: &str, as_reference: bool) {
@@ -1280,6 +1776,18 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_path_ordering() {
+        let path = |value| Path::try_from(value).unwrap();
+
+        assert_eq!(path("/%61/b"), path("/a/b"));
+        assert_eq!(path("/%61/b").cmp(&path("/a/b")), Ordering::Equal);
+        assert!(path("/a") < path("/a/b"));
+        assert!(path("/a/b") < path("/b"));
+        assert!(path("b") < path("/a"));
+        assert_ne!(path("/a"), path("a"));
+    }
+
     #[test]
     fn test_path_parse() {
         use self::PathError::*;
@@ -1306,6 +1814,85 @@ mod test {
         assert_eq!(Path::try_from("%zz"), Err(InvalidPercentEncoding));
     }
 
+    #[test]
+    fn test_path_parse_non_utf8() {
+        assert_eq!(
+            Path::try_from(&b"/caf\xE9"[..]),
+            Err(PathError::InvalidCharacter)
+        );
+        assert_eq!(
+            Path::try_from(&b"/\xC3\xA9"[..]),
+            Err(PathError::InvalidCharacter)
+        );
+
+        let path = Path::try_from(&b"/caf%E9/%FF%FE"[..]).unwrap();
+        assert!(path.segments().iter().all(|segment| segment.is_ascii()));
+        assert_eq!(path, "/caf%E9/%FF%FE");
+
+        let segments = path.segments();
+        assert_eq!(segments[0].decode_bytes(), &b"caf\xE9"[..]);
+        assert_eq!(segments[0].decode(), "caf\u{fffd}");
+        assert_eq!(segments[1].decode_bytes(), &b"\xFF\xFE"[..]);
+        assert!(matches!(
+            Segment::try_from("raw").unwrap().decode_bytes(),
+            Cow::Borrowed(b"raw")
+        ));
+    }
+
+    #[test]
+    fn test_path_pop() {
+        let mut path = Path::try_from("/a/b").unwrap();
+        assert_eq!(path.pop().unwrap(), "b");
+        assert_eq!(path, "/a");
+        assert_eq!(path.pop().unwrap(), "a");
+        assert_eq!(path, "/");
+        assert_eq!(path.pop(), None);
+        assert_eq!(path, "/");
+        assert_eq!(path.segments().len(), 1);
+
+        let mut path = Path::try_from("a/..").unwrap();
+        assert_eq!(path.pop().unwrap(), "..");
+        assert_eq!(path.double_dot_segment_count, 0);
+        assert_eq!(path.pop().unwrap(), "a");
+        assert_eq!(path, "");
+        assert_eq!(path.pop(), None);
+        assert!(path.is_relative());
+    }
+
+    #[test]
+    fn test_path_push() {
+        let mut path = Path::try_from("").unwrap();
+        path.push("a").unwrap();
+        assert_eq!(path, "a");
+        path.push("b").unwrap();
+        assert_eq!(path, "a/b");
+
+        let mut path = Path::try_from("/").unwrap();
+        path.push("a").unwrap();
+        assert_eq!(path, "/a");
+        path.push("b").unwrap();
+        assert_eq!(path, "/a/b");
+        path.push("").unwrap();
+        assert_eq!(path, "/a/b/");
+
+        let mut path = Path::try_from("a").unwrap();
+        path.push("..").unwrap();
+        assert_eq!(path, "a/..");
+        assert_eq!(path.double_dot_segment_count, 1);
+        assert_eq!(path.leading_double_dot_segment_count, 0);
+
+        let mut path = Path::try_from("").unwrap();
+        path.push("..").unwrap();
+        path.push("..").unwrap();
+        assert_eq!(path, "../..");
+        assert_eq!(path.leading_double_dot_segment_count, 2);
+        assert!(path.is_normalized(true));
+
+        let mut path = Path::try_from("/a").unwrap();
+        assert_eq!(path.push("b/c"), Err(PathError::InvalidCharacter));
+        assert_eq!(path, "/a");
+    }
+
     #[test]
     fn test_path_remove_dot_segments() {
         fn test_case(value: &str, expected: &str) {
@@ -1337,6 +1924,95 @@ mod test {
         test_case("/a/b/./../", "/a/");
         test_case("/a/b/c/./../../g", "/a/g");
         test_case("mid/content=5/../6", "mid/6");
+        test_case("../a", "a");
+        test_case("../../a/b", "a/b");
+        test_case("./../a", "a");
+        test_case("/a/b/c/.", "/a/b/c/");
+        test_case("/a/b/c/..", "/a/b/");
+        test_case("/../a", "/a");
+        test_case("/../../a/b", "/a/b");
+        test_case("/a/b/c/../../../../g", "/g");
+        test_case("/a/%62/../c", "/a/c");
+
+        let mut path = Path::try_from("/a/%62/..").unwrap();
+        assert!(!path.is_normalized(false));
+        path.remove_dot_segments();
+        assert!(path.is_normalized(false));
+    }
+
+    #[test]
+    fn test_segment_decode() {
+        fn test_case(value: &str, expected: &str) {
+            let segment = Segment::try_from(value).unwrap();
+            assert_eq!(segment.decode(), expected);
+        }
+
+        test_case("", "");
+        test_case("segment", "segment");
+        test_case("a%20b", "a b");
+        test_case("a%2Fb", "a/b");
+        test_case("caf%C3%A9", "caf\u{e9}");
+        test_case("%FF", "\u{fffd}");
+    }
+
+    #[test]
+    fn test_segment_hash_map() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(Segment::try_from("segment").unwrap(), 1);
+        assert_eq!(map.get(&Segment::try_from("s%65gment").unwrap()), Some(&1));
+        assert_eq!(map.get(&Segment::try_from("other").unwrap()), None);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("segment"), 1);
+        let segment = Segment::try_from("segment").unwrap();
+        assert_eq!(map.get(segment.as_str()), Some(&1));
+        assert_eq!(map.get(&*segment), Some(&1));
+    }
+
+    #[test]
+    fn test_segment_from_unencoded() {
+        fn test_case(value: &str, expected: &str) {
+            let segment = Segment::from_unencoded(value);
+            assert_eq!(segment.as_str(), expected);
+            assert!(segment.is_normalized());
+            assert_eq!(Segment::try_from(segment.as_str()).unwrap(), segment);
+            assert_eq!(segment.decode(), value);
+        }
+
+        test_case("", "");
+        test_case("segment", "segment");
+        test_case("a/b", "a%2Fb");
+        test_case("a b", "a%20b");
+        test_case("100%", "100%25");
+        test_case("a:b@c", "a:b@c");
+        test_case("\u{e9}t\u{e9}", "%C3%A9t%C3%A9");
+    }
+
+    #[test]
+    fn test_segment_new_unchecked() {
+        fn test_case(value: &str) {
+            // Safety: The test cases are all valid.
+            let mut segment = unsafe { Segment::new_unchecked(value) };
+            let mut expected = Segment::try_from(value).unwrap();
+            assert_eq!(segment, expected);
+            assert_eq!(segment.as_str(), value);
+
+            if segment.is_normalized() {
+                assert!(expected.is_normalized());
+            }
+
+            segment.normalize();
+            expected.normalize();
+            assert_eq!(segment.as_str(), expected.as_str());
+            assert!(segment.is_normalized());
+        }
+
+        test_case("segment");
+        test_case("a%20b");
+        test_case("%7e");
+        test_case("");
     }
 
     #[test]
@@ -1344,12 +2020,42 @@ mod test {
         fn test_case(value: &str, expected: &str) {
             let mut segment = Segment::try_from(value).unwrap();
             segment.normalize();
+            assert!(segment.is_normalized());
             assert_eq!(segment, expected);
         }
 
         test_case("", "");
         test_case("%ff", "%FF");
         test_case("%41", "A");
+        test_case("%2D%2E%5F%7E", "-._~");
+        test_case("%2f%3a", "%2F%3A");
+
+        assert!(!Segment::try_from("%41").unwrap().is_normalized());
+        assert!(!Segment::try_from("%2d").unwrap().is_normalized());
+        assert!(Segment::try_from("%2F").unwrap().is_normalized());
+    }
+
+    #[test]
+    fn test_segment_parameters() {
+        fn test_case(value: &str, expected: &[(&str, Option<&str>)]) {
+            let segment = Segment::try_from(value).unwrap();
+            let parameters: Vec<_> = segment.parameters().collect();
+            let expected: Vec<_> = expected
+                .iter()
+                .map(|(key, value)| (Cow::from(*key), value.map(Cow::from)))
+                .collect();
+            assert_eq!(parameters, expected);
+            assert_eq!(segment, value);
+        }
+
+        test_case("a;x=1;y", &[("x", Some("1")), ("y", None)]);
+        test_case("a", &[]);
+        test_case("", &[]);
+        test_case("a;", &[]);
+        test_case(";x", &[("x", None)]);
+        test_case("a;x=", &[("x", Some(""))]);
+        test_case("a;;x=1=2;", &[("x", Some("1=2"))]);
+        test_case("a;x%3Dy=%3B%20", &[("x=y", Some("; "))]);
     }
 
     #[test]
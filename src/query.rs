@@ -2,26 +2,33 @@
 //!
 //! See [[RFC3986, Section 3.4](https://tools.ietf.org/html/rfc3986#section-3.4)].
 //!
-//! This crate does not do query string parsing, it will simply make sure that it is a valid query
-//! string as defined by the RFC. You will need to use another crate (e.g.
-//! [queryst](https://github.com/rustless/queryst)) if you want it parsed.
+//! The RFC does not define any structure for the query, so parsing will simply make sure that it is
+//! a valid query string as defined by the RFC. However, since most queries are key/value pairs of
+//! the form `"key1=value1&key2=value2"`, the [`Query::pairs`] and [`Query::form_pairs`] functions
+//! can be used to iterate over them.
 
-use std::borrow::Cow;
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::ops::Deref;
+use core::str;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::ops::Deref;
-use std::str;
 
 use crate::utility::{
-    get_percent_encoded_value, normalize_string, percent_encoded_equality, percent_encoded_hash,
+    decode_percent_encoded, encode_percent_encoded, get_percent_encoded_value, normalize_string,
+    percent_decode, percent_encoded_cmp, percent_encoded_equality, percent_encoded_hash,
     UNRESERVED_CHAR_MAP,
 };
 
 /// A map of byte characters that determines if a character is a valid query character.
 #[rustfmt::skip]
-const QUERY_CHAR_MAP: [u8; 256] = [
+pub(crate) const QUERY_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -41,12 +48,45 @@ const QUERY_CHAR_MAP: [u8; 256] = [
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
 ];
 
+/// A map of byte characters that determines if a character can be used unencoded in a key or value
+/// of a query pair. This is the same as [`QUERY_CHAR_MAP`] except that `'&'`, `'+'`, and `'='` are
+/// not allowed.
+#[rustfmt::skip]
+const QUERY_PAIR_CHAR_MAP: [u8; 256] = [
+ // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
+    0, b'!',    0,    0, b'$', b'%',    0,b'\'', b'(', b')', b'*',    0, b',', b'-', b'.', b'/', // 2
+ b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b':', b';',    0,    0,    0, b'?', // 3
+ b'@', b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N', b'O', // 4
+ b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z',    0,    0,    0,    0, b'_', // 5
+    0, b'a', b'b', b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', // 6
+ b'p', b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z',    0,    0,    0, b'~',    0, // 7
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 8
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 9
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // A
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // B
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // C
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // D
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // E
+    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
+];
+
 /// The query component as defined in
 /// [[RFC3986, Section 3.4](https://tools.ietf.org/html/rfc3986#section-3.4)].
 ///
 /// The query is case-sensitive. Furthermore, percent-encoding plays no role in equality checking
 /// for characters in the unreserved character set meaning that `"query"` and `"que%72y"` are
-/// identical. Both of these attributes are reflected in the equality and hash functions.
+/// identical. Both of these attributes are reflected in the equality and hash functions. This also
+/// applies when comparing a query to a string or byte string.
+///
+/// The `'+'` character is not treated as a space when checking for equality, since that is only a
+/// convention of `application/x-www-form-urlencoded` queries. As such, `"a+b"`, `"a%20b"`, and
+/// `"a%2Bb"` are all different queries.
+///
+/// For the same reason, the query does not implement `Borrow<str>`, since a query such as
+/// `"que%72y"` would hash differently than the equal string `"query"`. A query can be used as the
+/// key of a map directly, or [`Query::as_str`] can be used to look it up in a map with string keys.
 ///
 /// However, be aware that just because percent-encoding plays no role in equality checking does not
 /// mean that the query is normalized. If the query needs to be normalized, use the
@@ -62,7 +102,7 @@ pub struct Query<'query> {
 
 impl Query<'_> {
     /// Returns a new query which is identical but has a lifetime tied to this query.
-    pub fn as_borrowed(&self) -> Query {
+    pub fn as_borrowed(&self) -> Query<'_> {
         use self::Cow::*;
 
         let query = match &self.query {
@@ -92,6 +132,123 @@ impl Query<'_> {
         &self.query
     }
 
+    /// Returns the query with all percent-encoded bytes decoded, without requiring the decoded bytes
+    /// to be valid UTF-8.
+    ///
+    /// Unlike [`Query::pairs`], the query is not split into pairs and `'+'` is left as is. If the
+    /// query contains no percent-encodings, the returned value will borrow from the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("key=%FF%FE").unwrap();
+    /// assert_eq!(query.decode_bytes(), &b"key=\xFF\xFE"[..]);
+    /// ```
+    pub fn decode_bytes(&self) -> Cow<'_, [u8]> {
+        percent_decode(self.query.as_bytes()).unwrap()
+    }
+
+    /// Returns an iterator over the percent-decoded key/value pairs of the query, treating `'+'` as
+    /// a space as done by `application/x-www-form-urlencoded`.
+    ///
+    /// This is identical to [`Query::pairs`] except for the handling of `'+'`. Note that a
+    /// percent-encoded `'+'` (i.e. `"%2B"`) will still be decoded as `'+'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("q=hello+world%2B").unwrap();
+    /// let pairs: Vec<_> = query.form_pairs().collect();
+    /// assert_eq!(pairs, vec![("q".into(), "hello world+".into())]);
+    /// ```
+    pub fn form_pairs(&self) -> QueryPairs<'_> {
+        QueryPairs {
+            plus_as_space: true,
+            query: &self.query,
+        }
+    }
+
+    /// Constructs a query from the given key/value pairs.
+    ///
+    /// Keys and values are percent-encoded as done by [`QueryBuilder::append_pair`], so they may
+    /// contain any character, and the pairs are joined with `'&'`. The result can be turned back
+    /// into the given pairs with [`Query::pairs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::from_pairs(vec![("a", "1"), ("b&c", ""), ("d", "e=f")]);
+    /// assert_eq!(query, "a=1&b%26c=&d=e%3Df");
+    /// ```
+    pub fn from_pairs<TPairs, TKey, TValue>(pairs: TPairs) -> Query<'static>
+    where
+        TPairs: IntoIterator<Item = (TKey, TValue)>,
+        TKey: AsRef<str>,
+        TValue: AsRef<str>,
+    {
+        let mut builder = QueryBuilder::new();
+
+        for (key, value) in pairs {
+            builder.append_pair(key.as_ref(), value.as_ref());
+        }
+
+        builder.build()
+    }
+
+    /// Returns the percent-decoded value of the first pair whose percent-decoded key is equal to
+    /// the given key.
+    ///
+    /// Pairs are iterated over as done by [`Query::pairs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("a%62c=1&abc=2").unwrap();
+    /// assert_eq!(query.get("abc").unwrap(), "1");
+    /// assert!(query.get("xyz").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<Cow<'_, str>> {
+        self.pairs()
+            .find(|(pair_key, _)| pair_key == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the percent-decoded values of all pairs whose percent-decoded key is equal to the
+    /// given key, in the order they appear in the query.
+    ///
+    /// Pairs are iterated over as done by [`Query::pairs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("a=1&b=2&a=3").unwrap();
+    /// assert_eq!(query.get_all("a"), vec!["1", "3"]);
+    /// ```
+    pub fn get_all(&self, key: &str) -> Vec<Cow<'_, str>> {
+        self.pairs()
+            .filter(|(pair_key, _)| pair_key == key)
+            .map(|(_, value)| value)
+            .collect()
+    }
+
     /// Converts the [`Query`] into an owned copy.
     ///
     /// If you construct the query from a source with a non-static lifetime, you may run into
@@ -107,6 +264,28 @@ impl Query<'_> {
         }
     }
 
+    /// Returns whether the query is empty.
+    ///
+    /// Note that an empty query is different from no query at all, e.g. the URI
+    /// `"http://example.com/?"` has an empty query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("").unwrap();
+    /// assert!(query.is_empty());
+    ///
+    /// let query = Query::try_from("a=b").unwrap();
+    /// assert!(!query.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.query.is_empty()
+    }
+
     /// Returns whether the query is normalized.
     ///
     /// A normalized query will have no bytes that are in the unreserved character set
@@ -133,6 +312,69 @@ impl Query<'_> {
         self.normalized
     }
 
+    /// Returns an iterator over the percent-decoded key/value pairs of the query.
+    ///
+    /// The query is split on `'&'`, and each pair is split on the first `'='`. Empty pairs are
+    /// skipped, and a key without a `'='` will have an empty value. The `'+'` character is not
+    /// treated specially, use [`Query::form_pairs`] if it should be decoded as a space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let query = Query::try_from("a=1&b&c=%3D").unwrap();
+    /// let pairs: Vec<_> = query.pairs().collect();
+    /// assert_eq!(
+    ///     pairs,
+    ///     vec![
+    ///         ("a".into(), "1".into()),
+    ///         ("b".into(), "".into()),
+    ///         ("c".into(), "=".into())
+    ///     ]
+    /// );
+    /// ```
+    pub fn pairs(&self) -> QueryPairs<'_> {
+        QueryPairs {
+            plus_as_space: false,
+            query: &self.query,
+        }
+    }
+
+    /// Constructs a query from the given string without checking that it is valid.
+    ///
+    /// This skips the validation done by [`Query::try_from`], which is useful when the value is
+    /// already known to be valid (e.g. because it was taken from another query). If the value
+    /// contains percent-encodings, the query will not be considered normalized until
+    /// [`Query::normalize`] is called, even if it already is.
+    ///
+    /// # Safety
+    ///
+    /// The value must be a valid query, that is it must only consist of characters allowed in a
+    /// query as defined in
+    /// [[RFC3986, Section 3.4](https://tools.ietf.org/html/rfc3986#section-3.4)], and every `'%'`
+    /// must be followed by two hexadecimal digits. Other functions of this crate, such as
+    /// [`Query::normalize`], rely on this to operate on the bytes of the query in place. Passing an
+    /// invalid value is undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Query;
+    ///
+    /// // Safety: The value is a valid query.
+    /// let query = unsafe { Query::new_unchecked("a=1&b=%3D") };
+    /// assert_eq!(query.as_str(), "a=1&b=%3D");
+    /// ```
+    pub unsafe fn new_unchecked(value: &str) -> Query<'_> {
+        Query {
+            normalized: !value.contains('%'),
+            query: Cow::Borrowed(value),
+        }
+    }
+
     /// Normalizes the query such that it will have no bytes that are in the unreserved character
     /// set percent-encoded and all alphabetical characters in percent-encodings will be uppercase.
     ///
@@ -159,10 +401,44 @@ impl Query<'_> {
     pub fn normalize(&mut self) {
         if !self.normalized {
             // Unsafe: Queries must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.query.to_mut(), true) };
+            unsafe { normalize_string(self.query.to_mut(), true) };
             self.normalized = true;
         }
     }
+
+    /// Sorts the key/value pairs of the query by their percent-decoded key, then by their
+    /// percent-decoded value.
+    ///
+    /// Keys and values are compared as percent-decoded bytes, so values that are not valid UTF-8
+    /// are never altered. The sort is stable, so pairs that are equal after decoding keep their
+    /// relative order. Empty pairs are removed, but the pairs themselves are kept exactly as they
+    /// are, including their percent-encodings, any `'+'` characters, and any missing `'='`. This
+    /// makes the result suitable as a canonical form, e.g. for cache keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Query;
+    ///
+    /// let mut query = Query::try_from("b=2&a=1&%61=0").unwrap();
+    /// query.sort_pairs();
+    /// assert_eq!(query.as_str(), "%61=0&a=1&b=2");
+    /// ```
+    pub fn sort_pairs(&mut self) {
+        let mut pairs: Vec<_> = raw_pairs(&self.query).collect();
+        pairs.sort_by_cached_key(|pair| {
+            let (key, value) = split_pair(pair);
+            (
+                percent_decode(key.as_bytes()).unwrap(),
+                percent_decode(value.as_bytes()).unwrap(),
+            )
+        });
+
+        let query = pairs.join("&");
+        self.query = Cow::Owned(query);
+    }
 }
 
 impl AsRef<[u8]> for Query<'_> {
@@ -208,6 +484,12 @@ impl Hash for Query<'_> {
     }
 }
 
+impl Ord for Query<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        percent_encoded_cmp(self.query.as_bytes(), other.query.as_bytes(), true)
+    }
+}
+
 impl PartialEq for Query<'_> {
     fn eq(&self, other: &Query) -> bool {
         percent_encoded_equality(self.query.as_bytes(), other.query.as_bytes(), true)
@@ -232,7 +514,7 @@ impl<'a> PartialEq<&'a [u8]> for Query<'_> {
     }
 }
 
-impl<'a, 'query> PartialEq<Query<'query>> for &'a [u8] {
+impl<'query> PartialEq<Query<'query>> for &[u8] {
     fn eq(&self, other: &Query<'query>) -> bool {
         percent_encoded_equality(self, other.query.as_bytes(), true)
     }
@@ -256,17 +538,23 @@ impl<'a> PartialEq<&'a str> for Query<'_> {
     }
 }
 
-impl<'a, 'query> PartialEq<Query<'query>> for &'a str {
+impl<'query> PartialEq<Query<'query>> for &str {
     fn eq(&self, other: &Query<'query>) -> bool {
         percent_encoded_equality(self.as_bytes(), other.query.as_bytes(), true)
     }
 }
 
+impl PartialOrd for Query<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'query> TryFrom<&'query [u8]> for Query<'query> {
     type Error = QueryError;
 
     fn try_from(value: &'query [u8]) -> Result<Self, Self::Error> {
-        let (query, rest) = parse_query(value)?;
+        let (query, rest) = parse_query(value).map_err(|(error, _)| error)?;
 
         if rest.is_empty() {
             Ok(query)
@@ -284,6 +572,130 @@ impl<'query> TryFrom<&'query str> for Query<'query> {
     }
 }
 
+/// The string is validated and then moved into the query without being copied.
+impl TryFrom<String> for Query<'static> {
+    type Error = QueryError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let normalized = Query::try_from(value.as_str())?.normalized;
+
+        Ok(Query {
+            normalized,
+            query: Cow::Owned(value),
+        })
+    }
+}
+
+/// A builder type for a [`Query`] made of key/value pairs (e.g. `"a=1&b=2"`).
+///
+/// Keys and values are percent-encoded as needed, including the `'&'`, `'+'`, and `'='`
+/// characters, so that the resulting query can be iterated over with [`Query::pairs`] or
+/// [`Query::form_pairs`] to get the original pairs back. Pairs are kept in insertion order.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QueryBuilder {
+    /// The query that has been built so far.
+    query: String,
+}
+
+impl QueryBuilder {
+    /// Appends a key/value pair to the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::QueryBuilder;
+    ///
+    /// let mut builder = QueryBuilder::new();
+    /// builder.append_pair("a", "1").append_pair("b", "hello world");
+    /// let query = builder.build();
+    /// assert_eq!(query.as_str(), "a=1&b=hello%20world");
+    /// ```
+    pub fn append_pair(&mut self, key: &str, value: &str) -> &mut Self {
+        if !self.query.is_empty() {
+            self.query.push('&');
+        }
+
+        self.query
+            .push_str(&encode_percent_encoded(key, &QUERY_PAIR_CHAR_MAP));
+        self.query.push('=');
+        self.query
+            .push_str(&encode_percent_encoded(value, &QUERY_PAIR_CHAR_MAP));
+        self
+    }
+
+    /// Consumes the builder and builds the [`Query`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::new().build();
+    /// assert_eq!(query.as_str(), "");
+    /// ```
+    pub fn build(self) -> Query<'static> {
+        Query {
+            normalized: true,
+            query: Cow::Owned(self.query),
+        }
+    }
+
+    /// Constructs a new builder with nothing set.
+    pub fn new() -> Self {
+        QueryBuilder::default()
+    }
+
+    /// Consumes the builder and appends a key/value pair to the query.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::QueryBuilder;
+    ///
+    /// let query = QueryBuilder::new()
+    ///     .with_pair("key", "a&b=c")
+    ///     .build();
+    /// assert_eq!(query.as_str(), "key=a%26b%3Dc");
+    /// ```
+    pub fn with_pair(mut self, key: &str, value: &str) -> Self {
+        self.append_pair(key, value);
+        self
+    }
+}
+
+/// An iterator over the percent-decoded key/value pairs of a query.
+///
+/// This is created by [`Query::pairs`] and [`Query::form_pairs`].
+#[derive(Clone, Debug)]
+pub struct QueryPairs<'query> {
+    /// Whether `'+'` should be decoded as a space.
+    plus_as_space: bool,
+
+    /// The remaining part of the query that has yet to be iterated over.
+    query: &'query str,
+}
+
+impl<'query> QueryPairs<'query> {
+    /// Percent-decodes the given key or value.
+    fn decode(&self, value: &'query str) -> Cow<'query, str> {
+        if self.plus_as_space && value.contains('+') {
+            Cow::Owned(decode_percent_encoded(&value.replace('+', " ")).into_owned())
+        } else {
+            decode_percent_encoded(value)
+        }
+    }
+}
+
+impl<'query> Iterator for QueryPairs<'query> {
+    type Item = (Cow<'query, str>, Cow<'query, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pair = next_raw_pair(&mut self.query)?;
+        let (key, value) = split_pair(pair);
+        Some((self.decode(key), self.decode(value)))
+    }
+}
+
 /// An error representing an invalid query.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
@@ -306,6 +718,7 @@ impl Display for QueryError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for QueryError {}
 
 impl From<Infallible> for QueryError {
@@ -315,7 +728,9 @@ impl From<Infallible> for QueryError {
 }
 
 /// Parses the query from the given byte string.
-pub(crate) fn parse_query(value: &[u8]) -> Result<(Query, &[u8]), QueryError> {
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+pub(crate) fn parse_query(value: &[u8]) -> Result<(Query<'_>, &[u8]), (QueryError, usize)> {
     let mut bytes = value.iter();
     let mut end_index = 0;
     let mut normalized = true;
@@ -323,7 +738,7 @@ pub(crate) fn parse_query(value: &[u8]) -> Result<(Query, &[u8]), QueryError> {
     while let Some(&byte) = bytes.next() {
         match QUERY_CHAR_MAP[byte as usize] {
             0 if byte == b'#' => break,
-            0 => return Err(QueryError::InvalidCharacter),
+            0 => return Err((QueryError::InvalidCharacter, end_index)),
             b'%' => match get_percent_encoded_value(bytes.next().cloned(), bytes.next().cloned()) {
                 Ok((hex_value, uppercase)) => {
                     if !uppercase || UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
@@ -332,7 +747,7 @@ pub(crate) fn parse_query(value: &[u8]) -> Result<(Query, &[u8]), QueryError> {
 
                     end_index += 3;
                 }
-                Err(_) => return Err(QueryError::InvalidPercentEncoding),
+                Err(_) => return Err((QueryError::InvalidPercentEncoding, end_index)),
             },
             _ => end_index += 1,
         }
@@ -348,21 +763,311 @@ pub(crate) fn parse_query(value: &[u8]) -> Result<(Query, &[u8]), QueryError> {
     Ok((query, rest))
 }
 
+/// Removes the next raw pair from the front of the given query string and returns it, skipping
+/// empty pairs.
+fn next_raw_pair<'query>(query: &mut &'query str) -> Option<&'query str> {
+    while !query.is_empty() {
+        let (pair, rest) = match query.find('&') {
+            Some(index) => (&query[..index], &query[index + 1..]),
+            None => (*query, ""),
+        };
+        *query = rest;
+
+        if !pair.is_empty() {
+            return Some(pair);
+        }
+    }
+
+    None
+}
+
+/// Returns the percent-decoded key of the given raw pair, as done by [`Query::pairs`].
+pub(crate) fn raw_pair_key(pair: &str) -> Cow<'_, str> {
+    decode_percent_encoded(split_pair(pair).0)
+}
+
+/// Returns the raw pairs of the given query string, without percent-decoding them. Empty pairs are
+/// skipped, as done by [`Query::pairs`].
+pub(crate) fn raw_pairs(mut query: &str) -> impl Iterator<Item = &str> {
+    iter::from_fn(move || next_raw_pair(&mut query))
+}
+
+/// Splits the given pair into its key and value on the first `'='`. A pair without a `'='` has an
+/// empty value.
+pub(crate) fn split_pair(pair: &str) -> (&str, &str) {
+    match pair.find('=') {
+        Some(index) => (&pair[..index], &pair[index + 1..]),
+        None => (pair, ""),
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+
     use super::*;
 
+    #[test]
+    fn test_query_builder() {
+        let mut builder = QueryBuilder::new();
+        builder
+            .append_pair("a", "1")
+            .append_pair("b", "hello world");
+        let query = builder.build();
+        assert_eq!(query.as_str(), "a=1&b=hello%20world");
+        assert!(query.is_normalized());
+
+        let pairs = [
+            ("a", "1"),
+            ("key with spaces", "x&y=z"),
+            ("plus+", "100%"),
+            ("", ""),
+            ("a", "\u{2713}"),
+        ];
+        let mut builder = QueryBuilder::new();
+
+        for (key, value) in pairs.iter() {
+            builder.append_pair(key, value);
+        }
+
+        let query = builder.build();
+        assert_eq!(
+            query.as_str(),
+            "a=1&key%20with%20spaces=x%26y%3Dz&plus%2B=100%25&=&a=%E2%9C%93"
+        );
+        assert_eq!(Query::try_from(query.as_str()).unwrap(), query);
+
+        let parsed: Vec<_> = query.pairs().collect();
+        assert_eq!(parsed.len(), pairs.len());
+
+        for ((key, value), (expected_key, expected_value)) in parsed.iter().zip(pairs.iter()) {
+            assert_eq!(key, expected_key);
+            assert_eq!(value, expected_value);
+        }
+
+        let parsed: Vec<_> = query.form_pairs().collect();
+        assert_eq!(parsed[2], (Cow::from("plus+"), Cow::from("100%")));
+    }
+
+    #[test]
+    fn test_query_eq_str() {
+        let query = Query::try_from("a%62c=1").unwrap();
+        assert_eq!(query, "abc=1");
+        assert_eq!(query, *"abc=1");
+        assert_eq!("abc=1", query);
+        assert_eq!(*"abc=1", query);
+        assert_eq!(query, "a%62c=1");
+        assert_eq!(query, "%61bc=%31");
+        assert_eq!(query, Query::try_from("abc=1").unwrap());
+
+        assert_ne!(query, "abc=2");
+        assert_ne!(query, "ABC=1");
+        assert_ne!(query, "abc");
+        assert_ne!("abc=1&d", query);
+
+        let query = Query::try_from("a+b").unwrap();
+        assert_ne!(query, "a%20b");
+        assert_ne!(query, "a%2Bb");
+        assert_eq!(
+            Query::try_from("a%3Db").unwrap(),
+            Query::try_from("a%3db").unwrap()
+        );
+        assert_ne!(Query::try_from("a%3Db").unwrap(), "a=b");
+    }
+
+    #[test]
+    fn test_query_from_pairs() {
+        let pairs = vec![
+            ("a", "1"),
+            ("empty", ""),
+            ("", "no key"),
+            ("reserved&=?/#", "a+b%c"),
+            ("a", "2"),
+        ];
+        let query = Query::from_pairs(pairs.clone());
+        assert_eq!(
+            query.as_str(),
+            "a=1&empty=&=no%20key&reserved%26%3D?/%23=a%2Bb%25c&a=2"
+        );
+        assert!(matches!(query.query, Cow::Owned(_)));
+        assert_eq!(Query::try_from(query.as_str()).unwrap(), query);
+
+        let parsed: Vec<_> = query.pairs().collect();
+        let expected: Vec<_> = pairs
+            .iter()
+            .map(|&(key, value)| (Cow::from(key), Cow::from(value)))
+            .collect();
+        assert_eq!(parsed, expected);
+
+        let mut map = BTreeMap::new();
+        map.insert(String::from("b"), String::from("2"));
+        map.insert(String::from("a"), String::from("hello world"));
+        let query = Query::from_pairs(&map);
+        assert_eq!(query, "a=hello%20world&b=2");
+
+        let parsed: BTreeMap<_, _> = query
+            .pairs()
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        assert_eq!(parsed, map);
+
+        assert_eq!(Query::from_pairs(Vec::<(&str, &str)>::new()), "");
+    }
+
+    #[test]
+    fn test_query_hash_map() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(Query::try_from("a=b").unwrap(), 1);
+        assert_eq!(map.get(&Query::try_from("a=b").unwrap()), Some(&1));
+        assert_eq!(map.get(&Query::try_from("%61=%62").unwrap()), Some(&1));
+        assert_eq!(map.get(&Query::try_from("a=c").unwrap()), None);
+
+        let mut map = HashMap::new();
+        map.insert(String::from("a=b"), 1);
+        let query = Query::try_from("a=b").unwrap();
+        assert_eq!(map.get(query.as_str()), Some(&1));
+        assert_eq!(map.get(&*query), Some(&1));
+    }
+
+    #[test]
+    fn test_query_get() {
+        let query = Query::try_from("a=1&b=2&a=3&a%62c=x%20y&empty").unwrap();
+
+        assert_eq!(query.get("a").unwrap(), "1");
+        assert_eq!(query.get("b").unwrap(), "2");
+        assert_eq!(query.get("abc").unwrap(), "x y");
+        assert_eq!(query.get("empty").unwrap(), "");
+        assert_eq!(query.get("c"), None);
+        assert_eq!(query.get("a%62c"), None);
+
+        assert_eq!(query.get_all("a"), vec!["1", "3"]);
+        assert_eq!(query.get_all("b"), vec!["2"]);
+        assert!(query.get_all("c").is_empty());
+    }
+
+    #[test]
+    fn test_query_new_unchecked() {
+        fn test_case(value: &str) {
+            // Safety: The test cases are all valid.
+            let mut query = unsafe { Query::new_unchecked(value) };
+            let mut expected = Query::try_from(value).unwrap();
+            assert_eq!(query, expected);
+            assert_eq!(query.as_str(), value);
+
+            if query.is_normalized() {
+                assert!(expected.is_normalized());
+            }
+
+            query.normalize();
+            expected.normalize();
+            assert_eq!(query.as_str(), expected.as_str());
+            assert!(query.is_normalized());
+        }
+
+        test_case("a=1&b=2");
+        test_case("a=%3D");
+        test_case("%7e");
+        test_case("");
+    }
+
     #[test]
     fn test_query_normalize() {
         fn test_case(value: &str, expected: &str) {
             let mut query = Query::try_from(value).unwrap();
             query.normalize();
+            assert!(query.is_normalized());
             assert_eq!(query, expected);
         }
 
         test_case("", "");
         test_case("%ff", "%FF");
         test_case("%41", "A");
+        test_case("%2D%2E%5F%7E", "-._~");
+        test_case("%2f%3a", "%2F%3A");
+
+        assert!(!Query::try_from("%41").unwrap().is_normalized());
+        assert!(!Query::try_from("%2d").unwrap().is_normalized());
+        assert!(Query::try_from("%2F").unwrap().is_normalized());
+    }
+
+    #[test]
+    fn test_query_owned() {
+        let value = String::from("qu%65ry");
+        let pointer = value.as_ptr();
+        let query = Query::try_from(value).unwrap();
+        assert_eq!(query.as_str().as_ptr(), pointer);
+        assert!(!query.is_normalized());
+
+        assert_eq!(
+            Query::try_from(String::from("a b")),
+            Err(QueryError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn test_query_pairs() {
+        fn test_case(value: &str, expected: &[(&str, &str)]) {
+            let query = Query::try_from(value).unwrap();
+            let pairs: Vec<_> = query.pairs().collect();
+            assert_eq!(pairs.len(), expected.len());
+
+            for ((key, value), (expected_key, expected_value)) in pairs.iter().zip(expected) {
+                assert_eq!(key, expected_key);
+                assert_eq!(value, expected_value);
+            }
+        }
+
+        test_case("", &[]);
+        test_case("a=1&b=2", &[("a", "1"), ("b", "2")]);
+        test_case("a&b=2", &[("a", ""), ("b", "2")]);
+        test_case("a=", &[("a", "")]);
+        test_case("a=1&a=2", &[("a", "1"), ("a", "2")]);
+        test_case("a=x%3Dy", &[("a", "x=y")]);
+        test_case("a=x=y", &[("a", "x=y")]);
+        test_case("a=1&&b=2&", &[("a", "1"), ("b", "2")]);
+        test_case("k%20ey=v+al", &[("k ey", "v+al")]);
+        test_case("%E2%9C%93=1", &[("\u{2713}", "1")]);
+
+        let query = Query::try_from("a=1").unwrap();
+
+        match query.pairs().next().unwrap() {
+            (Cow::Borrowed(key), Cow::Borrowed(value)) => assert_eq!((key, value), ("a", "1")),
+            _ => panic!("expected borrowed pair"),
+        }
+
+        let query = Query::try_from("q=hello+world%2B&a+b").unwrap();
+        let pairs: Vec<_> = query.form_pairs().collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (Cow::from("q"), Cow::from("hello world+")),
+                (Cow::from("a b"), Cow::from(""))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_sort_pairs() {
+        fn test_case(value: &str, expected: &str) {
+            let mut query = Query::try_from(value).unwrap();
+            query.sort_pairs();
+            assert_eq!(query.as_str(), expected);
+        }
+
+        test_case("b=2&a=1", "a=1&b=2");
+        test_case("a=1&b=2", "a=1&b=2");
+        test_case("%62=1&a=2&c=3", "a=2&%62=1&c=3");
+        test_case("%2A=1&a=2", "%2A=1&a=2");
+        test_case("a=2&a=1&A=3", "A=3&a=1&a=2");
+        test_case("b&&a=x%2fy%26z", "a=x%2fy%26z&b");
+        test_case("b=1&a=%FF", "a=%FF&b=1");
+        test_case("x=1+2&a=%ff%fe", "a=%ff%fe&x=1+2");
+        test_case("c&b=&a", "a&b=&c");
+        test_case("a=2&a&a=1", "a&a=1&a=2");
+        test_case("", "");
     }
 
     #[test]
@@ -1,13 +1,19 @@
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter};
 
 use crate::authority::{Authority, AuthorityError, Host, Password, Username};
 use crate::fragment::{Fragment, FragmentError};
 use crate::path::{Path, PathError};
 use crate::query::{Query, QueryError};
 use crate::scheme::Scheme;
-use crate::uri_reference::{URIReference, URIReferenceBuilder, URIReferenceError};
+use crate::uri_reference::{
+    Components, PathMut, URIReference, URIReferenceBuilder, URIReferenceError,
+};
 
 /// A relative reference as defined in
 /// [[RFC3986, Section 4.1]](https://tools.ietf.org/html/rfc3986#section-4.1).
@@ -21,6 +27,30 @@ pub struct RelativeReference<'uri> {
 }
 
 impl<'uri> RelativeReference<'uri> {
+    /// Returns a new relative reference which is identical but has a lifetime tied to this
+    /// relative reference.
+    ///
+    /// All components borrow from this relative reference, so no component string is copied. Only
+    /// the list of path segments is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/path#fragment").unwrap().into_owned();
+    /// let borrowed = reference.as_borrowed();
+    /// assert_eq!(borrowed, reference);
+    /// assert_eq!(borrowed.to_string(), "/path#fragment");
+    /// ```
+    pub fn as_borrowed(&self) -> RelativeReference<'_> {
+        RelativeReference {
+            uri_reference: self.uri_reference.as_borrowed(),
+        }
+    }
+
     pub fn as_uri_reference(&self) -> &URIReference<'uri> {
         &self.uri_reference
     }
@@ -64,6 +94,27 @@ impl<'uri> RelativeReference<'uri> {
         RelativeReferenceBuilder::new()
     }
 
+    /// Returns an iterator over the components that are present in the relative reference, in the
+    /// order in which they appear.
+    ///
+    /// Since relative references have no scheme, [`Component::Scheme`] is never yielded.
+    ///
+    /// [`Component::Scheme`]: crate::Component::Scheme
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/path?query").unwrap();
+    /// assert_eq!(reference.components().count(), 2);
+    /// ```
+    pub fn components(&self) -> Components<'_> {
+        self.uri_reference.components()
+    }
+
     /// Constructs a new [`RelativeReference`] from the individual parts: authority, path, query,
     /// and fragment.
     ///
@@ -131,6 +182,26 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.fragment()
     }
 
+    /// Returns a mutable reference to the fragment of the relative reference.
+    ///
+    /// Since the fragment has no effect on whether the other components are valid, it can be
+    /// changed or removed freely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Fragment, RelativeReference};
+    ///
+    /// let mut reference = RelativeReference::try_from("/path#fragment").unwrap();
+    /// *reference.fragment_mut() = Some(Fragment::try_from("other").unwrap());
+    /// assert_eq!(reference.fragment().unwrap(), "other");
+    /// ```
+    pub fn fragment_mut(&mut self) -> &mut Option<Fragment<'uri>> {
+        self.uri_reference.fragment_mut()
+    }
+
     /// Returns whether the relative reference has an authority component.
     ///
     /// # Examples
@@ -261,6 +332,29 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.host()
     }
 
+    /// Returns the string representation of the host, if present, of the relative reference.
+    ///
+    /// This is the same as formatting [`RelativeReference::host`], so IP literals are enclosed in
+    /// brackets. Registered names are borrowed, while IP addresses have to be formatted into a new
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("//username@example.com").unwrap();
+    /// assert_eq!(reference.host_str().unwrap(), "example.com");
+    ///
+    /// let reference = RelativeReference::try_from("//[::1]:8080").unwrap();
+    /// assert_eq!(reference.host_str().unwrap(), "[::1]");
+    /// ```
+    pub fn host_str(&self) -> Option<Cow<'_, str>> {
+        self.uri_reference.host_str()
+    }
+
     /// Consumes the relative reference and converts it into a builder with the same values.
     ///
     /// # Examples
@@ -334,6 +428,25 @@ impl<'uri> RelativeReference<'uri> {
         (authority, path, query, fragment)
     }
 
+    /// Converts the relative reference into a [`URIReference`].
+    ///
+    /// This is the same as using the [`From`] implementation, but does not require type annotations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/my/path").unwrap();
+    /// let reference = reference.into_uri_reference();
+    /// assert!(reference.is_relative_reference());
+    /// ```
+    pub fn into_uri_reference(self) -> URIReference<'uri> {
+        self.uri_reference
+    }
+
     /// Returns whether the relative reference is an absolute path reference.
     ///
     /// A URI reference is an absolute path reference if it is a relative reference that begins with
@@ -544,6 +657,28 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.path()
     }
 
+    /// Returns a mutable reference to the path of the relative reference.
+    ///
+    /// Some changes to the path could make it invalid in combination with the other components,
+    /// so a guard is returned instead of a plain reference. When the guard is dropped, the path is
+    /// adjusted in the same way as [`URIReferenceBuilder::fix_path`], without changing what the
+    /// relative reference resolves to (e.g. it is made absolute if there is an authority).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let mut reference = RelativeReference::try_from("//example.com/my/path").unwrap();
+    /// reference.path_mut().push("other").unwrap();
+    /// assert_eq!(reference.path(), "/my/path/other");
+    /// ```
+    pub fn path_mut(&mut self) -> PathMut<'_, 'uri> {
+        self.uri_reference.path_mut()
+    }
+
     /// Returns the password, if present, of the relative reference.
     ///
     /// Usage of a password in URI and URI references is deprecated.
@@ -594,6 +729,26 @@ impl<'uri> RelativeReference<'uri> {
         self.uri_reference.query()
     }
 
+    /// Returns a mutable reference to the query of the relative reference.
+    ///
+    /// Since the query has no effect on whether the other components are valid, it can be changed
+    /// or removed freely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let mut reference = RelativeReference::try_from("//example.com/path?b=2&a=1").unwrap();
+    /// reference.query_mut().as_mut().unwrap().sort_pairs();
+    /// assert_eq!(reference.query().unwrap(), "a=1&b=2");
+    /// ```
+    pub fn query_mut(&mut self) -> &mut Option<Query<'uri>> {
+        self.uri_reference.query_mut()
+    }
+
     /// Sets the authority of the relative reference.
     ///
     /// An error will be returned if the conversion to an [`Authority`] fails.
@@ -728,6 +883,43 @@ impl<'uri> RelativeReference<'uri> {
     pub fn username(&self) -> Option<&Username<'uri>> {
         self.uri_reference.username()
     }
+
+    /// Consumes the relative reference and returns it without its fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let reference = RelativeReference::try_from("/path#fragment").unwrap();
+    /// assert_eq!(reference.without_fragment().to_string(), "/path");
+    /// ```
+    pub fn without_fragment(mut self) -> Self {
+        self.uri_reference = self.uri_reference.without_fragment();
+        self
+    }
+
+    /// Appends the relative reference to the given string.
+    ///
+    /// The relative reference is formatted with [`Display`] directly into the string, so no
+    /// intermediate string is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::RelativeReference;
+    ///
+    /// let mut string = String::new();
+    /// RelativeReference::try_from("/path?query").unwrap().write_to(&mut string);
+    /// assert_eq!(string, "/path?query");
+    /// ```
+    pub fn write_to(&self, out: &mut String) {
+        self.uri_reference.write_to(out);
+    }
 }
 
 impl Display for RelativeReference<'_> {
@@ -738,7 +930,9 @@ impl Display for RelativeReference<'_> {
 
 impl<'uri> From<RelativeReference<'uri>> for String {
     fn from(value: RelativeReference<'uri>) -> Self {
-        value.to_string()
+        let mut string = String::new();
+        value.write_to(&mut string);
+        string
     }
 }
 
@@ -748,6 +942,14 @@ impl<'uri> From<RelativeReference<'uri>> for URIReference<'uri> {
     }
 }
 
+impl FromStr for RelativeReference<'static> {
+    type Err = RelativeReferenceError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(RelativeReference::try_from(value.as_bytes())?.into_owned())
+    }
+}
+
 impl<'uri> TryFrom<&'uri [u8]> for RelativeReference<'uri> {
     type Error = RelativeReferenceError;
 
@@ -859,6 +1061,28 @@ impl<'uri> RelativeReferenceBuilder<'uri> {
         })
     }
 
+    /// Adjusts the path so that it can be combined with the authority that is currently set,
+    /// without changing what the relative reference resolves to.
+    ///
+    /// See [`URIReferenceBuilder::fix_path`] for the adjustments that are made.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, RelativeReferenceBuilder};
+    ///
+    /// let mut builder = RelativeReferenceBuilder::new();
+    /// builder.path(Path::try_from("//my/path").unwrap()).fix_path();
+    /// let reference = builder.build().unwrap();
+    /// assert_eq!(reference.to_string(), "/.//my/path");
+    /// ```
+    pub fn fix_path(&mut self) -> &mut Self {
+        self.uri_reference_builder.fix_path();
+        self
+    }
+
     /// Sets the fragment part of the relative reference.
     ///
     /// It is optional to specify a fragment.
@@ -1200,6 +1424,44 @@ pub enum RelativeReferenceError {
     SchemelessPathStartsWithColonSegment,
 }
 
+impl RelativeReferenceError {
+    /// Returns the kind of this error, without any nested component error.
+    ///
+    /// Unlike [`RelativeReferenceError`] itself, the returned [`RelativeReferenceErrorKind`] is a
+    /// plain discriminant, which makes it convenient to match on or to store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{RelativeReference, RelativeReferenceErrorKind};
+    ///
+    /// assert_eq!(
+    ///     RelativeReference::try_from("http://example.com").unwrap_err().kind(),
+    ///     RelativeReferenceErrorKind::NotRelativeReference
+    /// );
+    /// ```
+    pub fn kind(&self) -> RelativeReferenceErrorKind {
+        use self::RelativeReferenceError::*;
+
+        match self {
+            AbsolutePathStartsWithTwoSlashes => {
+                RelativeReferenceErrorKind::AbsolutePathStartsWithTwoSlashes
+            }
+            Authority(_) => RelativeReferenceErrorKind::Authority,
+            Fragment(_) => RelativeReferenceErrorKind::Fragment,
+            Path(_) => RelativeReferenceErrorKind::Path,
+            Query(_) => RelativeReferenceErrorKind::Query,
+            MissingPath => RelativeReferenceErrorKind::MissingPath,
+            NotRelativeReference => RelativeReferenceErrorKind::NotRelativeReference,
+            SchemelessPathStartsWithColonSegment => {
+                RelativeReferenceErrorKind::SchemelessPathStartsWithColonSegment
+            }
+        }
+    }
+}
+
 impl Display for RelativeReferenceError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         use self::RelativeReferenceError::*;
@@ -1222,8 +1484,39 @@ impl Display for RelativeReferenceError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for RelativeReferenceError {}
 
+/// The kind of an error representing an invalid relative reference, as returned by
+/// [`RelativeReferenceError::kind`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum RelativeReferenceErrorKind {
+    /// See [`RelativeReferenceError::AbsolutePathStartsWithTwoSlashes`].
+    AbsolutePathStartsWithTwoSlashes,
+
+    /// See [`RelativeReferenceError::Authority`].
+    Authority,
+
+    /// See [`RelativeReferenceError::Fragment`].
+    Fragment,
+
+    /// See [`RelativeReferenceError::MissingPath`].
+    MissingPath,
+
+    /// See [`RelativeReferenceError::Path`].
+    Path,
+
+    /// See [`RelativeReferenceError::Query`].
+    Query,
+
+    /// See [`RelativeReferenceError::NotRelativeReference`].
+    NotRelativeReference,
+
+    /// See [`RelativeReferenceError::SchemelessPathStartsWithColonSegment`].
+    SchemelessPathStartsWithColonSegment,
+}
+
 impl From<Infallible> for RelativeReferenceError {
     fn from(_: Infallible) -> Self {
         RelativeReferenceError::MissingPath
@@ -1276,3 +1569,78 @@ impl TryFrom<URIReferenceError> for RelativeReferenceError {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+    use crate::authority::PortError;
+
+    #[test]
+    fn test_conversions() {
+        let reference = RelativeReference::try_from("//example.com/path?query").unwrap();
+        let uri_reference = URIReference::from(reference.clone());
+        assert_eq!(uri_reference.to_string(), "//example.com/path?query");
+        assert_eq!(reference.clone().into_uri_reference(), uri_reference);
+        assert_eq!(
+            RelativeReference::try_from(uri_reference).unwrap(),
+            reference
+        );
+
+        let uri_reference = URIReference::try_from("http://example.com/path").unwrap();
+        assert_eq!(
+            RelativeReference::try_from(uri_reference),
+            Err(RelativeReferenceError::NotRelativeReference)
+        );
+    }
+
+    #[test]
+    fn test_error_kind() {
+        use std::collections::HashSet;
+
+        let errors = [
+            RelativeReferenceError::AbsolutePathStartsWithTwoSlashes,
+            RelativeReferenceError::Authority(AuthorityError::Port(PortError::InvalidCharacter)),
+            RelativeReferenceError::Fragment(FragmentError::InvalidPercentEncoding { index: 0 }),
+            RelativeReferenceError::MissingPath,
+            RelativeReferenceError::NotRelativeReference,
+            RelativeReferenceError::Path(PathError::InvalidCharacter),
+            RelativeReferenceError::Query(QueryError::InvalidCharacter),
+            RelativeReferenceError::SchemelessPathStartsWithColonSegment,
+        ];
+        let kinds: HashSet<RelativeReferenceErrorKind> =
+            errors.iter().map(RelativeReferenceError::kind).collect();
+        assert_eq!(kinds.len(), errors.len());
+
+        assert_eq!(
+            RelativeReference::try_from("//[::1").unwrap_err().kind(),
+            RelativeReferenceErrorKind::Authority
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let reference: RelativeReference<'static> = "/path?query#fragment".parse().unwrap();
+        assert_eq!(reference.to_string(), "/path?query#fragment");
+
+        let reference = {
+            let value = String::from("//example.com/path");
+            value.parse::<RelativeReference>().unwrap()
+        };
+        assert!(reference.has_authority());
+
+        assert_eq!(
+            "http://example.com/".parse::<RelativeReference>(),
+            Err(RelativeReferenceError::NotRelativeReference)
+        );
+        assert_eq!(
+            "a:b".parse::<RelativeReference>(),
+            Err(RelativeReferenceError::NotRelativeReference)
+        );
+        assert_eq!(
+            "/a b".parse::<RelativeReference>(),
+            Err(RelativeReferenceError::Path(PathError::InvalidCharacter))
+        );
+    }
+}
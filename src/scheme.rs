@@ -6,28 +6,28 @@
 //! the listed schemes, see
 //! [iana.org](https://www.iana.org/assignments/uri-schemes/uri-schemes.xhtml).
 
-use fnv::FnvBuildHasher;
-use lazy_static::lazy_static;
-use std::borrow::Cow;
-use std::collections::HashMap;
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str::{self, FromStr};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::str;
-
-use crate::utility::normalize_string;
 
 /// The length of the longest currently registered scheme. This is used internally for parsing. Make
 /// sure to check this whenever adding a new scheme.
 const MAX_REGISTERED_SCHEME_LENGTH: usize = 36;
 
-/// The number of registered schemes. Make sure to update this whenever adding a new scheme.
+/// The number of registered schemes. Make sure to update this whenever adding a new scheme. The
+/// schemes must also be kept sorted by name.
 const NUMBER_OF_SCHEMES: usize = 304;
 
 /// A map of byte characters that determines if a character is a valid scheme character.
 #[rustfmt::skip]
-const SCHEME_CHAR_MAP: [u8; 256] = [
+pub(crate) const SCHEME_CHAR_MAP: [u8; 256] = [
  // 0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 0
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // 1
@@ -53,22 +53,13 @@ macro_rules! schemes {
             ($variant:ident, $name:expr, $status:expr);
         )+
     ) => {
-        lazy_static! {
-            /// An immutable hashmap mapping scheme names to their corresponding [`Scheme`]
-            /// variants.
-            static ref SCHEME_NAME_MAP: HashMap<&'static [u8], Scheme<'static>, FnvBuildHasher> = {
-                let mut map = HashMap::with_capacity_and_hasher(
-                    NUMBER_OF_SCHEMES,
-                    FnvBuildHasher::default()
-                );
-
-            $(
-                assert!(map.insert($name.as_bytes(), Scheme::$variant).is_none());
-            )+
-
-                map
-            };
-        }
+        /// An array of scheme names and their corresponding [`Scheme`] variants, sorted by name so
+        /// that it can be binary searched.
+        static SCHEME_NAMES: [(&[u8], Scheme<'static>); NUMBER_OF_SCHEMES] = [
+        $(
+            ($name.as_bytes(), Scheme::$variant),
+        )+
+        ];
 
         /// The scheme component as defined in
         /// [[RFC3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5)]. The schemes
@@ -90,7 +81,7 @@ macro_rules! schemes {
 
         impl<'scheme> Scheme<'scheme> {
             /// Returns a new scheme which is identical but has a lifetime tied to this scheme.
-            pub fn as_borrowed(&self) -> Scheme {
+            pub fn as_borrowed(&self) -> Scheme<'_> {
                 use self::Scheme::*;
 
                 match self {
@@ -172,8 +163,13 @@ macro_rules! schemes {
         }
 
         /// Parses the scheme from the given byte string.
-        pub(crate) fn parse_scheme(value: &[u8]) -> Result<(Scheme, &[u8]), SchemeError> {
-            fn unregistered_scheme(value: &[u8], normalized: bool) -> Scheme {
+        ///
+        /// On failure, the index of the byte at which the error occurred is returned along with the
+        /// error.
+        pub(crate) fn parse_scheme(
+            value: &[u8],
+        ) -> Result<(Scheme<'_>, &[u8]), (SchemeError, usize)> {
+            fn unregistered_scheme(value: &[u8], normalized: bool) -> Scheme<'_> {
                 // Unsafe: The loop below makes sure the byte string is valid ASCII-US.
                 let scheme = unsafe { str::from_utf8_unchecked(value) };
                 Scheme::Unregistered(UnregisteredScheme{
@@ -182,52 +178,187 @@ macro_rules! schemes {
                 })
             }
 
-            if !value.iter().next().ok_or(SchemeError::Empty)?.is_ascii_alphabetic() {
-                return Err(SchemeError::StartsWithNonAlphabetic);
+            if !value.iter().next().ok_or((SchemeError::Empty, 0))?.is_ascii_alphabetic() {
+                return Err((SchemeError::StartsWithNonAlphabetic, 0));
             }
 
             let mut end_index = 0;
-            let mut lowercase_scheme = [0; MAX_REGISTERED_SCHEME_LENGTH];
             let mut normalized = true;
 
             for &byte in value.iter() {
                 match SCHEME_CHAR_MAP[byte as usize] {
                     0 if byte == b':' => break,
-                    0 => return Err(SchemeError::InvalidCharacter),
+                    0 => return Err((SchemeError::InvalidCharacter, end_index)),
                     _ => {
-                        if byte >= b'A' && byte <= b'Z' {
+                        if byte.is_ascii_uppercase() {
                             normalized = false;
                         }
 
-                        if end_index + 1 < MAX_REGISTERED_SCHEME_LENGTH {
-                            lowercase_scheme[end_index] = byte.to_ascii_lowercase();
-                        }
-
                         end_index += 1;
                     }
                 }
             }
 
             let (value, rest) = value.split_at(end_index);
-
-            // It is important to make sure that [`MAX_REGISTERED_SCHEME_LENGTH`] is correctly
-            // maintained, or registered schemes may be set as unregistered.
-
-            if end_index > MAX_REGISTERED_SCHEME_LENGTH {
-                return Ok((unregistered_scheme(value, normalized), rest));
-            }
-
-            let scheme = SCHEME_NAME_MAP
-                .get(&lowercase_scheme[..end_index])
-                .cloned()
-                .unwrap_or_else(|| unregistered_scheme(value, normalized));
+            let scheme =
+                registered_scheme(value).unwrap_or_else(|| unregistered_scheme(value, normalized));
 
             Ok((scheme, rest))
         }
     }
 }
 
+/// Returns the registered scheme with the given case-insensitive name, if there is one.
+fn registered_scheme(value: &[u8]) -> Option<Scheme<'static>> {
+    // It is important to make sure that [`MAX_REGISTERED_SCHEME_LENGTH`] is correctly maintained,
+    // or registered schemes may be set as unregistered.
+
+    if value.len() > MAX_REGISTERED_SCHEME_LENGTH {
+        return None;
+    }
+
+    let mut lowercase_scheme = [0; MAX_REGISTERED_SCHEME_LENGTH];
+    let lowercase_scheme = &mut lowercase_scheme[..value.len()];
+    lowercase_scheme.copy_from_slice(value);
+    lowercase_scheme.make_ascii_lowercase();
+
+    SCHEME_NAMES
+        .binary_search_by_key(&&*lowercase_scheme, |&(name, _)| name)
+        .ok()
+        .map(|index| SCHEME_NAMES[index].1.clone())
+}
+
+/// How URIs with a given scheme use the authority component.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AuthorityUsage {
+    /// URIs with the scheme cannot have an authority.
+    Forbidden,
+
+    /// URIs with the scheme may have an authority.
+    Optional,
+
+    /// URIs with the scheme conventionally have an authority.
+    Required,
+}
+
 impl Scheme<'_> {
+    /// Returns whether URIs with the scheme may have an authority component.
+    ///
+    /// This is `false` for registered schemes whose specification has no authority in its syntax,
+    /// such as `"data"`, `"mailto"`, `"tel"`, and `"urn"`. All other schemes, including
+    /// unregistered ones, are assumed to allow an authority.
+    ///
+    /// This is less strict than [`Scheme::uses_authority`]: every scheme that uses an authority
+    /// allows one, but schemes such as `"sip"` and unregistered schemes allow an authority without
+    /// conventionally having one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::HTTP.allows_authority());
+    /// assert!(!Scheme::try_from("MAILTO").unwrap().allows_authority());
+    /// assert!(Scheme::try_from("test-scheme").unwrap().allows_authority());
+    /// ```
+    pub fn allows_authority(&self) -> bool {
+        self.authority_usage() != AuthorityUsage::Forbidden
+    }
+
+    /// Returns how URIs with the scheme use the authority component.
+    ///
+    /// Both [`Scheme::allows_authority`] and [`Scheme::uses_authority`] are derived from this.
+    fn authority_usage(&self) -> AuthorityUsage {
+        use self::Scheme::*;
+
+        match self {
+            ACAP | AFP | CoAP | CoAPS | DICT | File | Finger | FTP | Git | Gopher | HTTP
+            | HTTPS | IMAP | IPP | IPPS | IRC | IRCS | LDAP | LDAPS | MSRP | MSRPS | MTQP | NFS
+            | NNTP | POP | Redis | RedisS | RSync | RTSP | RTSPS | RTSPU | SFTP | Sieve | SMB
+            | SMTP | SNMP | SSH | SVN | Telnet | TFTP | VNC | WS | WSS => AuthorityUsage::Required,
+            Data | Geo | MailTo | SMS | Tag | Tel | URN => AuthorityUsage::Forbidden,
+            _ => AuthorityUsage::Optional,
+        }
+    }
+
+    /// Returns the well-known default port of the scheme, if it has one.
+    ///
+    /// Unregistered schemes and registered schemes without a well-known port will return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert_eq!(Scheme::HTTP.default_port(), Some(80));
+    /// assert_eq!(Scheme::try_from("HTTPS").unwrap().default_port(), Some(443));
+    /// assert_eq!(Scheme::try_from("test-scheme").unwrap().default_port(), None);
+    /// ```
+    pub fn default_port(&self) -> Option<u16> {
+        use self::Scheme::*;
+
+        let port = match self {
+            ACAP => 674,
+            AFP => 548,
+            CoAP => 5683,
+            CoAPS => 5684,
+            DICT => 2628,
+            DNS => 53,
+            Finger => 79,
+            FTP => 21,
+            Git => 9418,
+            Gopher => 70,
+            HTTP => 80,
+            HTTPS => 443,
+            IMAP => 143,
+            IPP => 631,
+            IPPS => 631,
+            IRC => 194,
+            IRCS => 994,
+            LDAP => 389,
+            LDAPS => 636,
+            MSRP => 2855,
+            MSRPS => 2855,
+            MTQP => 1038,
+            News => 119,
+            NFS => 2049,
+            NNTP => 119,
+            POP => 110,
+            Redis => 6379,
+            RedisS => 6379,
+            RSync => 873,
+            RTSP => 554,
+            RTSPS => 322,
+            RTSPU => 5005,
+            SFTP => 22,
+            Sieve => 4190,
+            SIP => 5060,
+            SIPS => 5061,
+            SMB => 445,
+            SMTP => 25,
+            SNMP => 161,
+            SSH => 22,
+            STUN => 3478,
+            STUNS => 5349,
+            SVN => 3690,
+            Telnet => 23,
+            TFTP => 69,
+            TURN => 3478,
+            TURNS => 5349,
+            VNC => 5900,
+            WS => 80,
+            WSS => 443,
+            XMPP => 5222,
+            _ => return None,
+        };
+
+        Some(port)
+    }
+
     /// Returns whether the scheme is normalized.
     ///
     /// A normalized scheme will be all lowercase. All standardized schemes are always considered
@@ -260,6 +391,83 @@ impl Scheme<'_> {
         }
     }
 
+    /// Returns whether the scheme is permanently registered in the
+    /// [IANA registry](https://www.iana.org/assignments/uri-schemes/uri-schemes.xhtml).
+    ///
+    /// Provisional and historical schemes are listed in the registry too, but are not considered
+    /// registered. Use [`Scheme::status`] to distinguish between them. Since parsing is
+    /// case-insensitive, so is this check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::try_from("HTTP").unwrap().is_registered());
+    /// assert!(!Scheme::try_from("acr").unwrap().is_registered());
+    /// assert!(!Scheme::try_from("zzz").unwrap().is_registered());
+    /// ```
+    pub fn is_registered(&self) -> bool {
+        self.status().is_permanent()
+    }
+
+    /// Returns whether the scheme is a special scheme as defined by the
+    /// [WHATWG URL Standard](https://url.spec.whatwg.org/#special-scheme).
+    ///
+    /// The special schemes are `"file"`, `"ftp"`, `"http"`, `"https"`, `"ws"`, and `"wss"`. The
+    /// `"gopher"` scheme used to be special as well, but has since been removed from the standard.
+    /// Note that `"file"` is special even though it has no default port.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::HTTPS.is_special());
+    /// assert!(Scheme::try_from("FILE").unwrap().is_special());
+    /// assert!(!Scheme::MailTo.is_special());
+    /// ```
+    pub fn is_special(&self) -> bool {
+        use self::Scheme::*;
+
+        matches!(self, File | FTP | HTTP | HTTPS | WS | WSS)
+    }
+
+    /// Constructs a scheme from the given string without checking that it is valid.
+    ///
+    /// This skips the validation done by [`Scheme::try_from`], but registered schemes are still
+    /// recognized, so `"HTTP"` will result in [`Scheme::HTTP`].
+    ///
+    /// # Safety
+    ///
+    /// The value must be a valid scheme as defined in
+    /// [[RFC3986, Section 3.1](https://tools.ietf.org/html/rfc3986#section-3.1)], that is it must
+    /// start with an ASCII letter followed only by ASCII letters, digits, `'+'`, `'-'`, and `'.'`.
+    /// Other parts of this crate rely on schemes being valid ASCII. Passing an invalid value is
+    /// undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// // Safety: The values are valid schemes.
+    /// assert_eq!(unsafe { Scheme::new_unchecked("HTTP") }, Scheme::HTTP);
+    /// assert_eq!(unsafe { Scheme::new_unchecked("test-scheme") }, "test-scheme");
+    /// ```
+    pub unsafe fn new_unchecked(value: &str) -> Scheme<'_> {
+        registered_scheme(value.as_bytes()).unwrap_or_else(|| {
+            Scheme::Unregistered(UnregisteredScheme {
+                normalized: !value.bytes().any(|byte| byte.is_ascii_uppercase()),
+                scheme: Cow::Borrowed(value),
+            })
+        })
+    }
+
     /// Normalizes the scheme so that it is all lowercase.
     ///
     /// # Examples
@@ -275,13 +483,34 @@ impl Scheme<'_> {
     ///
     /// let mut scheme = Scheme::try_from("MyScHeMe").unwrap();
     /// scheme.normalize();
-    /// assert_eq!(scheme, "myscheme");
+    /// assert_eq!(scheme.as_str(), "myscheme");
     /// ```
     pub fn normalize(&mut self) {
         if let Scheme::Unregistered(scheme) = self {
             scheme.normalize();
         }
     }
+
+    /// Returns whether the scheme conventionally requires an authority component (i.e. URIs of the
+    /// scheme are of the form `"scheme://authority/path"`).
+    ///
+    /// This is true for registered hierarchical schemes such as `"file"`, `"ftp"`, `"http"`, and
+    /// `"ssh"`. Schemes such as `"mailto"`, `"news"`, `"sip"`, `"urn"`, and `"xmpp"`, whose URIs
+    /// usually have no authority even though they may have a [`Scheme::default_port`], and all
+    /// unregistered schemes, are not considered to use an authority.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uriparse::Scheme;
+    ///
+    /// assert!(Scheme::HTTP.uses_authority());
+    /// assert!(Scheme::File.uses_authority());
+    /// assert!(!Scheme::URN.uses_authority());
+    /// ```
+    pub fn uses_authority(&self) -> bool {
+        self.authority_usage() == AuthorityUsage::Required
+    }
 }
 
 impl AsRef<[u8]> for Scheme<'_> {
@@ -296,6 +525,14 @@ impl AsRef<str> for Scheme<'_> {
     }
 }
 
+impl Deref for Scheme<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
 impl Display for Scheme<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         formatter.write_str(self.as_str())
@@ -308,6 +545,48 @@ impl<'scheme> From<Scheme<'scheme>> for String {
     }
 }
 
+impl FromStr for Scheme<'static> {
+    type Err = SchemeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Scheme::try_from(value.as_bytes())?.into_owned())
+    }
+}
+
+impl Ord for Scheme<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.as_str().bytes().map(|byte| byte.to_ascii_lowercase());
+        let right = other.as_str().bytes().map(|byte| byte.to_ascii_lowercase());
+        left.cmp(right).then_with(|| {
+            matches!(self, Scheme::Unregistered(_)).cmp(&matches!(other, Scheme::Unregistered(_)))
+        })
+    }
+}
+
+impl PartialEq<[u8]> for Scheme<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_str().as_bytes().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'scheme> PartialEq<Scheme<'scheme>> for [u8] {
+    fn eq(&self, other: &Scheme<'scheme>) -> bool {
+        self.eq_ignore_ascii_case(other.as_bytes())
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for Scheme<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_str().as_bytes().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'scheme> PartialEq<Scheme<'scheme>> for &[u8] {
+    fn eq(&self, other: &Scheme<'scheme>) -> bool {
+        self.eq_ignore_ascii_case(other.as_bytes())
+    }
+}
+
 impl PartialEq<str> for Scheme<'_> {
     fn eq(&self, other: &str) -> bool {
         self.as_str().eq_ignore_ascii_case(other)
@@ -326,17 +605,23 @@ impl<'a> PartialEq<&'a str> for Scheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<Scheme<'scheme>> for &'a str {
+impl<'scheme> PartialEq<Scheme<'scheme>> for &str {
     fn eq(&self, other: &Scheme<'scheme>) -> bool {
         self.eq_ignore_ascii_case(other.as_str())
     }
 }
 
+impl PartialOrd for Scheme<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'scheme> TryFrom<&'scheme [u8]> for Scheme<'scheme> {
     type Error = SchemeError;
 
     fn try_from(value: &'scheme [u8]) -> Result<Scheme<'scheme>, Self::Error> {
-        let (scheme, rest) = parse_scheme(value)?;
+        let (scheme, rest) = parse_scheme(value).map_err(|(error, _)| error)?;
 
         if rest.is_empty() {
             Ok(scheme)
@@ -354,6 +639,23 @@ impl<'scheme> TryFrom<&'scheme str> for Scheme<'scheme> {
     }
 }
 
+/// An unregistered scheme takes ownership of the string without copying it.
+impl TryFrom<String> for Scheme<'static> {
+    type Error = SchemeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let normalized = match Scheme::try_from(value.as_str())? {
+            Scheme::Unregistered(scheme) => scheme.normalized,
+            scheme => return Ok(scheme.into_owned()),
+        };
+
+        Ok(Scheme::Unregistered(UnregisteredScheme {
+            normalized,
+            scheme: Cow::Owned(value),
+        }))
+    }
+}
+
 /// A scheme that is not in the
 /// [registered schemes](https://www.iana.org/assignments/uri-schemes/uri-schemes.xhtml).
 ///
@@ -370,7 +672,7 @@ pub struct UnregisteredScheme<'scheme> {
 impl UnregisteredScheme<'_> {
     /// Returns a new unregistered scheme which is identical but has a lifetime tied to this
     /// unregistered scheme.
-    pub fn as_borrowed(&self) -> UnregisteredScheme {
+    pub fn as_borrowed(&self) -> UnregisteredScheme<'_> {
         use self::Cow::*;
 
         let scheme = match &self.scheme {
@@ -457,12 +759,11 @@ impl UnregisteredScheme<'_> {
     ///
     /// let mut scheme = UnregisteredScheme::try_from("MyScHeMe").unwrap();
     /// scheme.normalize();
-    /// assert_eq!(scheme, "myscheme");
+    /// assert_eq!(scheme.as_str(), "myscheme");
     /// ```
     pub fn normalize(&mut self) {
         if !self.normalized {
-            // Unsafe: Schemes must be valid ASCII-US, so this is safe.
-            unsafe { normalize_string(&mut self.scheme.to_mut(), true) };
+            self.scheme.to_mut().make_ascii_lowercase();
             self.normalized = true;
         }
     }
@@ -480,6 +781,14 @@ impl AsRef<str> for UnregisteredScheme<'_> {
     }
 }
 
+impl Deref for UnregisteredScheme<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.scheme
+    }
+}
+
 impl Display for UnregisteredScheme<'_> {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         formatter.write_str(&self.scheme)
@@ -494,6 +803,14 @@ impl<'scheme> From<UnregisteredScheme<'scheme>> for String {
     }
 }
 
+impl FromStr for UnregisteredScheme<'static> {
+    type Err = UnregisteredSchemeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(UnregisteredScheme::try_from(value.as_bytes())?.into_owned())
+    }
+}
+
 impl Hash for UnregisteredScheme<'_> {
     fn hash<H>(&self, state: &mut H)
     where
@@ -503,12 +820,44 @@ impl Hash for UnregisteredScheme<'_> {
     }
 }
 
+impl Ord for UnregisteredScheme<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let left = self.scheme.bytes().map(|byte| byte.to_ascii_lowercase());
+        let right = other.scheme.bytes().map(|byte| byte.to_ascii_lowercase());
+        left.cmp(right)
+    }
+}
+
 impl PartialEq for UnregisteredScheme<'_> {
     fn eq(&self, other: &UnregisteredScheme) -> bool {
         self.scheme.eq_ignore_ascii_case(&other.scheme)
     }
 }
 
+impl PartialEq<[u8]> for UnregisteredScheme<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.scheme.as_bytes().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'scheme> PartialEq<UnregisteredScheme<'scheme>> for [u8] {
+    fn eq(&self, other: &UnregisteredScheme<'scheme>) -> bool {
+        self.eq_ignore_ascii_case(other.as_bytes())
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for UnregisteredScheme<'_> {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.scheme.as_bytes().eq_ignore_ascii_case(other)
+    }
+}
+
+impl<'scheme> PartialEq<UnregisteredScheme<'scheme>> for &[u8] {
+    fn eq(&self, other: &UnregisteredScheme<'scheme>) -> bool {
+        self.eq_ignore_ascii_case(other.as_bytes())
+    }
+}
+
 impl PartialEq<str> for UnregisteredScheme<'_> {
     fn eq(&self, other: &str) -> bool {
         self.scheme.eq_ignore_ascii_case(other)
@@ -527,12 +876,18 @@ impl<'a> PartialEq<&'a str> for UnregisteredScheme<'_> {
     }
 }
 
-impl<'a, 'scheme> PartialEq<UnregisteredScheme<'scheme>> for &'a str {
+impl<'scheme> PartialEq<UnregisteredScheme<'scheme>> for &str {
     fn eq(&self, other: &UnregisteredScheme<'scheme>) -> bool {
         self.eq_ignore_ascii_case(&other.scheme)
     }
 }
 
+impl PartialOrd for UnregisteredScheme<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<'scheme> TryFrom<&'scheme [u8]> for UnregisteredScheme<'scheme> {
     type Error = UnregisteredSchemeError;
 
@@ -580,6 +935,7 @@ impl Display for SchemeError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for SchemeError {}
 
 impl From<Infallible> for SchemeError {
@@ -599,6 +955,7 @@ impl Display for UnregisteredSchemeError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for UnregisteredSchemeError {}
 
 /// The registration status of a scheme. See [RFC 7595](https://tools.ietf.org/html/rfc7595) for
@@ -632,10 +989,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::HTTP.status().is_historical(), false);
     /// ```
     pub fn is_historical(self) -> bool {
-        match self {
-            SchemeStatus::Historical => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Historical)
     }
 
     /// Returns whether the scheme status is historical.
@@ -649,10 +1003,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::IRC.status().is_permanent(), false);
     /// ```
     pub fn is_permanent(self) -> bool {
-        match self {
-            SchemeStatus::Permanent => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Permanent)
     }
 
     /// Returns whether the scheme status is historical.
@@ -666,10 +1017,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::RTSP.status().is_provisional(), false);
     /// ```
     pub fn is_provisional(self) -> bool {
-        match self {
-            SchemeStatus::Provisional => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Provisional)
     }
 
     /// Returns whether the scheme status is historical.
@@ -686,10 +1034,7 @@ impl SchemeStatus {
     /// assert_eq!(Scheme::HTTPS.status().is_unregistered(), false);
     /// ```
     pub fn is_unregistered(self) -> bool {
-        match self {
-            SchemeStatus::Unregistered => true,
-            _ => false,
-        }
+        matches!(self, SchemeStatus::Unregistered)
     }
 }
 
@@ -698,7 +1043,7 @@ schemes! {
     (AAAS, "aaas", SchemeStatus::Permanent);
     (About, "about", SchemeStatus::Permanent);
     (ACAP, "acap", SchemeStatus::Permanent);
-    (ACCT, "acat", SchemeStatus::Permanent);
+    (ACCT, "acct", SchemeStatus::Permanent);
     (ACR, "acr", SchemeStatus::Provisional);
     (AdiumXtra, "adiumxtra", SchemeStatus::Provisional);
     (AFP, "afp", SchemeStatus::Provisional);
@@ -844,11 +1189,10 @@ schemes! {
     (MSMixedRealityCapture, "ms-mixedrealitycapture", SchemeStatus::Provisional);
     (MSOfficeApp, "ms-officeapp", SchemeStatus::Provisional);
     (MSPeople, "ms-people", SchemeStatus::Provisional);
-    (MSProject, "ms-project", SchemeStatus::Provisional);
     (MSPowerPoint, "ms-powerpoint", SchemeStatus::Provisional);
+    (MSProject, "ms-project", SchemeStatus::Provisional);
     (MSPublisher, "ms-publisher", SchemeStatus::Provisional);
     (MSRestoreTabCompanion, "ms-restoretabcompanion", SchemeStatus::Provisional);
-    (MSS, "mss", SchemeStatus::Provisional);
     (MSScreenClip, "ms-screenclip", SchemeStatus::Provisional);
     (MSScreenSketch, "ms-screensketch", SchemeStatus::Provisional);
     (MSSearch, "ms-search", SchemeStatus::Provisional);
@@ -888,6 +1232,7 @@ schemes! {
     (MSNIM, "msnim", SchemeStatus::Provisional);
     (MSRP, "msrp", SchemeStatus::Permanent);
     (MSRPS, "msrps", SchemeStatus::Permanent);
+    (MSS, "mss", SchemeStatus::Provisional);
     (MTQP, "mtqp", SchemeStatus::Permanent);
     (Mumble, "mumble", SchemeStatus::Provisional);
     (MUpdate, "mupdate", SchemeStatus::Permanent);
@@ -913,8 +1258,8 @@ schemes! {
     (Pres, "pres", SchemeStatus::Permanent);
     (Prospero, "prospero", SchemeStatus::Historical);
     (Proxy, "proxy", SchemeStatus::Provisional);
-    (PWID, "pwid", SchemeStatus::Provisional);
     (PSYC, "psyc", SchemeStatus::Provisional);
+    (PWID, "pwid", SchemeStatus::Provisional);
     (QB, "qb", SchemeStatus::Provisional);
     (Query, "query", SchemeStatus::Provisional);
     (Redis, "redis", SchemeStatus::Provisional);
@@ -936,9 +1281,9 @@ schemes! {
     (SGN, "sgn", SchemeStatus::Provisional);
     (SHTTP, "shttp", SchemeStatus::Permanent);
     (Sieve, "sieve", SchemeStatus::Permanent);
+    (SimpleLedger, "simpleledger", SchemeStatus::Provisional);
     (SIP, "sip", SchemeStatus::Permanent);
     (SIPS, "sips", SchemeStatus::Permanent);
-    (SimpleLedger, "simpleledger", SchemeStatus::Provisional);
     (Skype, "skype", SchemeStatus::Provisional);
     (SMB, "smb", SchemeStatus::Provisional);
     (SMS, "sms", SchemeStatus::Permanent);
@@ -978,8 +1323,8 @@ schemes! {
     (VEMMI, "vemmi", SchemeStatus::Permanent);
     (Ventrilo, "ventrilo", SchemeStatus::Provisional);
     (Videotex, "videotex", SchemeStatus::Historical);
-    (VNC, "vnc", SchemeStatus::Permanent);
     (ViewSource, "view-source", SchemeStatus::Provisional);
+    (VNC, "vnc", SchemeStatus::Permanent);
     (WAIS, "wais", SchemeStatus::Historical);
     (Webcal, "webcal", SchemeStatus::Provisional);
     (WPID, "wpid", SchemeStatus::Historical);
@@ -1004,16 +1349,200 @@ schemes! {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_scheme_allows_authority() {
+        fn test_case(value: &str, expected: bool) {
+            let scheme = Scheme::try_from(value).unwrap();
+            assert_eq!(scheme.allows_authority(), expected);
+        }
+
+        test_case("http", true);
+        test_case("file", true);
+        test_case("ssh", true);
+        test_case("test-scheme", true);
+        test_case("data", false);
+        test_case("MAILTO", false);
+        test_case("tel", false);
+        test_case("urn", false);
+
+        for (name, _) in SCHEME_NAMES.iter() {
+            let scheme = Scheme::try_from(*name).unwrap();
+            assert!(!scheme.uses_authority() || scheme.allows_authority());
+        }
+    }
+
+    #[test]
+    fn test_scheme_default_port() {
+        fn test_case(value: &str, expected: Option<u16>) {
+            let scheme = Scheme::try_from(value).unwrap();
+            assert_eq!(scheme.default_port(), expected);
+        }
+
+        test_case("http", Some(80));
+        test_case("HTTP", Some(80));
+        test_case("https", Some(443));
+        test_case("ftp", Some(21));
+        test_case("ssh", Some(22));
+        test_case("telnet", Some(23));
+        test_case("smtp", Some(25));
+        test_case("dns", Some(53));
+        test_case("nntp", Some(119));
+        test_case("imap", Some(143));
+        test_case("irc", Some(194));
+        test_case("ldap", Some(389));
+        test_case("ws", Some(80));
+        test_case("WSS", Some(443));
+        test_case("gopher", Some(70));
+        test_case("file", None);
+        test_case("urn", None);
+        test_case("test-scheme", None);
+    }
+
+    #[test]
+    fn test_scheme_equality() {
+        let scheme = Scheme::try_from("http").unwrap();
+        assert_eq!(scheme, "HTTP");
+        assert_eq!(scheme, *"HTTP");
+        assert_eq!("HTTP", scheme);
+        assert_eq!(*"HTTP", scheme);
+        assert_eq!(scheme, b"HtTp"[..]);
+        assert_eq!(scheme, &b"HtTp"[..]);
+        assert_eq!(b"HtTp"[..], scheme);
+        assert_eq!(&b"HtTp"[..], scheme);
+        assert_ne!(scheme, "https");
+        assert_eq!(&*scheme, "http");
+        assert_eq!(AsRef::<str>::as_ref(&scheme), "http");
+
+        let scheme = Scheme::try_from("MyScheme").unwrap();
+        assert_eq!(scheme, "myscheme");
+        assert_eq!("MYSCHEME", scheme);
+        assert_eq!(scheme, b"myscheme"[..]);
+        assert_eq!(&b"MYSCHEME"[..], scheme);
+        assert_eq!(&*scheme, "MyScheme");
+
+        let scheme = UnregisteredScheme::try_from("MyScheme").unwrap();
+        assert_eq!(scheme, "myscheme");
+        assert_eq!("MYSCHEME", scheme);
+        assert_eq!(scheme, b"myscheme"[..]);
+        assert_eq!(&b"MYSCHEME"[..], scheme);
+        assert_eq!(&*scheme, "MyScheme");
+    }
+
+    #[test]
+    fn test_scheme_from_str() {
+        let scheme: Scheme<'static> = "HTTP".parse().unwrap();
+        assert_eq!(scheme, Scheme::HTTP);
+
+        let scheme = {
+            let value = String::from("MyScheme");
+            value.parse::<Scheme>().unwrap()
+        };
+        assert_eq!(scheme, "myscheme");
+        assert_eq!(
+            "1http".parse::<Scheme>(),
+            Err(SchemeError::StartsWithNonAlphabetic)
+        );
+
+        let scheme: UnregisteredScheme<'static> = "MyScheme".parse().unwrap();
+        assert_eq!(scheme, "myscheme");
+        assert_eq!(
+            "http".parse::<UnregisteredScheme>(),
+            Err(UnregisteredSchemeError)
+        );
+    }
+
+    #[test]
+    fn test_scheme_is_registered() {
+        fn test_case(value: &str, expected: bool) {
+            assert_eq!(Scheme::try_from(value).unwrap().is_registered(), expected);
+        }
+
+        test_case("http", true);
+        test_case("HTTP", true);
+        test_case("about", true);
+        test_case("acct", true);
+        test_case("acr", false);
+        test_case("fax", false);
+        test_case("zzz", false);
+        test_case("acat", false);
+    }
+
+    #[test]
+    fn test_scheme_is_special() {
+        fn test_case(value: &str, expected: bool) {
+            let scheme = Scheme::try_from(value).unwrap();
+            assert_eq!(scheme.is_special(), expected);
+        }
+
+        test_case("http", true);
+        test_case("HTTPS", true);
+        test_case("ws", true);
+        test_case("wss", true);
+        test_case("ftp", true);
+        test_case("file", true);
+        test_case("FILE", true);
+        test_case("gopher", false);
+        test_case("ssh", false);
+        test_case("mailto", false);
+        test_case("test-scheme", false);
+    }
+
+    #[test]
+    fn test_scheme_new_unchecked() {
+        fn test_case(value: &str) {
+            // Safety: The test cases are all valid schemes.
+            let scheme = unsafe { Scheme::new_unchecked(value) };
+            let expected = Scheme::try_from(value).unwrap();
+            assert_eq!(scheme, expected);
+            assert_eq!(scheme.as_str(), expected.as_str());
+            assert_eq!(scheme.is_registered(), expected.is_registered());
+            assert_eq!(scheme.is_normalized(), expected.is_normalized());
+        }
+
+        test_case("http");
+        test_case("HTTPS");
+        test_case("ms-settings-workplace");
+        test_case("test-scheme");
+        test_case("Test-Scheme");
+        test_case("a");
+        test_case("a-very-long-scheme-that-is-not-registered-anywhere");
+    }
+
     #[test]
     fn test_scheme_normalize() {
         fn test_case(value: &str, expected: &str) {
             let mut scheme = Scheme::try_from(value).unwrap();
             scheme.normalize();
-            assert_eq!(scheme, expected);
+            assert!(scheme.is_normalized());
+            assert_eq!(scheme.as_str(), expected);
         }
 
         test_case("http", "http");
+        test_case("HTTP", "http");
         test_case("SCHEME", "scheme");
+        test_case("MyScHeMe+1.-", "myscheme+1.-");
+
+        let mut scheme = UnregisteredScheme::try_from("scheme").unwrap();
+        scheme.normalize();
+        assert!(matches!(scheme.scheme, Cow::Borrowed("scheme")));
+    }
+
+    #[test]
+    fn test_scheme_owned() {
+        let value = String::from("Test-Scheme");
+        let pointer = value.as_ptr();
+        let scheme = Scheme::try_from(value).unwrap();
+        assert_eq!(scheme.as_str().as_ptr(), pointer);
+        assert!(!scheme.is_normalized());
+
+        assert_eq!(
+            Scheme::try_from(String::from("HTTP")).unwrap(),
+            Scheme::HTTP
+        );
+        assert_eq!(
+            Scheme::try_from(String::from("1http")),
+            Err(SchemeError::StartsWithNonAlphabetic)
+        );
     }
 
     #[test]
@@ -1027,5 +1556,39 @@ mod test {
         assert_eq!(Scheme::try_from(""), Err(Empty));
         assert_eq!(Scheme::try_from("a:"), Err(InvalidCharacter));
         assert_eq!(Scheme::try_from("1"), Err(StartsWithNonAlphabetic));
+
+        for (name, scheme) in SCHEME_NAMES.iter() {
+            let parsed = Scheme::try_from(*name).unwrap();
+            assert_eq!(&parsed, scheme);
+            assert!(!parsed.status().is_unregistered());
+            assert!(name.len() <= MAX_REGISTERED_SCHEME_LENGTH);
+        }
+    }
+
+    #[test]
+    fn test_scheme_names_sorted() {
+        for window in SCHEME_NAMES.windows(2) {
+            assert!(window[0].0 < window[1].0);
+        }
+    }
+
+    #[test]
+    fn test_scheme_uses_authority() {
+        fn test_case(value: &str, expected: bool) {
+            let scheme = Scheme::try_from(value).unwrap();
+            assert_eq!(scheme.uses_authority(), expected);
+        }
+
+        test_case("http", true);
+        test_case("FTP", true);
+        test_case("file", true);
+        test_case("ssh", true);
+        test_case("mailto", false);
+        test_case("news", false);
+        test_case("sip", false);
+        test_case("urn", false);
+        test_case("tel", false);
+        test_case("xmpp", false);
+        test_case("test-scheme", false);
     }
 }
@@ -0,0 +1,137 @@
+//! [`proptest`](https://docs.rs/proptest) strategies for generating URIs and their components.
+//!
+//! All strategies only generate valid values, biased towards edge cases such as empty components,
+//! percent-encoded bytes and IPv6 hosts.
+
+use alloc::string::String;
+use core::convert::TryFrom;
+#[cfg(not(feature = "std"))]
+use core::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(feature = "std")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::string::{string_regex, RegexGeneratorStrategy};
+
+use crate::authority::{Authority, Host, IPV4_AND_REGISTERED_NAME_CHAR_MAP, USER_INFO_CHAR_MAP};
+use crate::fragment::{Fragment, FRAGMENT_CHAR_MAP};
+use crate::generate::{self, component_regex, MAX_SEGMENTS};
+use crate::path::{Path, PATH_CHAR_MAP};
+use crate::query::{Query, QUERY_CHAR_MAP};
+use crate::scheme::{Scheme, SCHEME_CHAR_MAP};
+use crate::uri::URI;
+
+/// Returns a strategy generating authorities.
+pub fn arb_authority() -> impl Strategy<Value = Authority<'static>> {
+    (
+        option::of((
+            component(&USER_INFO_CHAR_MAP, b":"),
+            option::of(component(&USER_INFO_CHAR_MAP, b"")),
+        )),
+        arb_host(),
+        option::of(prop_oneof![Just(0u16), Just(u16::MAX), any::<u16>()]),
+    )
+        .prop_map(|(user_info, host, port)| generate::authority(user_info, &host, port))
+}
+
+/// Returns a strategy generating fragments.
+pub fn arb_fragment() -> impl Strategy<Value = Fragment<'static>> {
+    prop_oneof![
+        1 => Just(String::new()),
+        4 => component(&FRAGMENT_CHAR_MAP, b""),
+    ]
+    .prop_map(|fragment| Fragment::try_from(fragment.as_str()).unwrap().into_owned())
+}
+
+/// Returns a strategy generating hosts, which may be IPv4 addresses, IPv6 addresses or registered
+/// names.
+pub fn arb_host() -> impl Strategy<Value = Host<'static>> {
+    prop_oneof![
+        any::<[u8; 4]>().prop_map(|octets| Host::from(Ipv4Addr::from(octets))),
+        any::<[u8; 16]>().prop_map(|octets| Host::from(Ipv6Addr::from(octets))),
+        Just(Host::from(Ipv6Addr::LOCALHOST)),
+        Just(Host::from(Ipv6Addr::UNSPECIFIED)),
+        component(&IPV4_AND_REGISTERED_NAME_CHAR_MAP, b"").prop_map(|name| Host::try_from(
+            name.as_str()
+        )
+        .unwrap()
+        .into_owned()),
+    ]
+}
+
+/// Returns a strategy generating paths, which may be absolute or relative.
+pub fn arb_path() -> impl Strategy<Value = Path<'static>> {
+    let segment = prop_oneof![
+        1 => Just(String::new()),
+        1 => Just(String::from(".")),
+        1 => Just(String::from("..")),
+        6 => component(&PATH_CHAR_MAP, b""),
+    ];
+
+    (any::<bool>(), vec(segment, 1..=MAX_SEGMENTS))
+        .prop_map(|(absolute, segments)| generate::path(absolute, &segments))
+}
+
+/// Returns a strategy generating queries.
+pub fn arb_query() -> impl Strategy<Value = Query<'static>> {
+    prop_oneof![
+        1 => Just(String::new()),
+        4 => component(&QUERY_CHAR_MAP, b""),
+    ]
+    .prop_map(|query| Query::try_from(query.as_str()).unwrap().into_owned())
+}
+
+/// Returns a strategy generating schemes, which may be registered or unregistered.
+pub fn arb_scheme() -> impl Strategy<Value = Scheme<'static>> {
+    prop_oneof![
+        Just(Scheme::HTTP),
+        Just(Scheme::HTTPS),
+        Just(Scheme::File),
+        Just(Scheme::URN),
+        ("[A-Za-z]", component(&SCHEME_CHAR_MAP, b"")).prop_map(|(first, rest)| {
+            let scheme = first + &rest;
+            Scheme::try_from(scheme.as_str()).unwrap().into_owned()
+        }),
+    ]
+}
+
+/// Returns a strategy generating URIs.
+pub fn arb_uri() -> impl Strategy<Value = URI<'static>> {
+    (
+        arb_scheme(),
+        option::of(arb_authority()),
+        arb_path(),
+        option::of(arb_query()),
+        option::of(arb_fragment()),
+    )
+        .prop_map(|(scheme, authority, path, query, fragment)| {
+            generate::uri(scheme, authority, path, query, fragment)
+        })
+}
+
+/// Returns a strategy generating strings of bytes allowed by the given character map, excluding
+/// the given excluded bytes, that may also contain percent-encoded bytes.
+fn component(char_map: &[u8; 256], excluded: &[u8]) -> RegexGeneratorStrategy<String> {
+    string_regex(&component_regex(char_map, excluded)).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_uri_round_trip(uri in arb_uri()) {
+            let string = uri.to_string();
+            prop_assert_eq!(URI::try_from(string.as_str()).unwrap(), uri);
+        }
+
+        #[test]
+        fn test_path_round_trip(path in arb_path()) {
+            let string = path.to_string();
+            prop_assert_eq!(Path::try_from(string.as_str()).unwrap(), path);
+        }
+    }
+}
@@ -1,13 +1,21 @@
-use std::convert::{Infallible, TryFrom};
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::cmp::Ordering;
+use core::convert::{Infallible, TryFrom};
+use core::fmt::{self, Display, Formatter, Write};
+use core::mem;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{self, Display, Formatter, Write};
-use std::mem;
 
 use crate::authority::{parse_authority, Authority, AuthorityError, Host, Password, Username};
 use crate::fragment::{Fragment, FragmentError};
 use crate::path::{parse_path, Path, PathError};
 use crate::query::{parse_query, Query, QueryError};
 use crate::scheme::{parse_scheme, Scheme, SchemeError};
+use crate::uri::URISpans;
 
 /// A URI reference as defined in
 /// [[RFC3986, Section 4.1]](https://tools.ietf.org/html/rfc3986#section-4.1).
@@ -37,6 +45,34 @@ pub struct URIReference<'uri> {
 }
 
 impl<'uri> URIReference<'uri> {
+    /// Returns a new URI reference which is identical but has a lifetime tied to this URI
+    /// reference.
+    ///
+    /// All components borrow from this URI reference, so no component string is copied. Only the
+    /// list of path segments is allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let reference = URIReference::try_from("//example.com/path?query").unwrap().into_owned();
+    /// let borrowed = reference.as_borrowed();
+    /// assert_eq!(borrowed, reference);
+    /// assert_eq!(borrowed.to_string(), "//example.com/path?query");
+    /// ```
+    pub fn as_borrowed(&self) -> URIReference<'_> {
+        URIReference {
+            authority: self.authority.as_ref().map(Authority::as_borrowed),
+            fragment: self.fragment.as_ref().map(Fragment::as_borrowed),
+            path: self.path.as_borrowed(),
+            query: self.query.as_ref().map(Query::as_borrowed),
+            scheme: self.scheme.as_ref().map(Scheme::as_borrowed),
+        }
+    }
+
     /// Returns the authority, if present, of the URI reference.
     ///
     /// # Examples
@@ -98,6 +134,32 @@ impl<'uri> URIReference<'uri> {
         self.has_scheme() && !self.has_fragment()
     }
 
+    /// Returns an iterator over the components that are present in the URI reference, in the order
+    /// in which they appear.
+    ///
+    /// The path is always present, even if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Component, URIReference};
+    ///
+    /// let reference = URIReference::try_from("//example.com/path#fragment").unwrap();
+    /// let mut components = reference.components();
+    /// assert!(matches!(components.next(), Some(Component::Authority(_))));
+    /// assert!(matches!(components.next(), Some(Component::Path(_))));
+    /// assert!(matches!(components.next(), Some(Component::Fragment(_))));
+    /// assert_eq!(components.next(), None);
+    /// ```
+    pub fn components(&self) -> Components<'_> {
+        Components {
+            next: 0,
+            uri_reference: self,
+        }
+    }
+
     /// Constructs a new [`URIReference`] from the individual parts: scheme, authority, path, query,
     /// and fragment.
     ///
@@ -205,6 +267,26 @@ impl<'uri> URIReference<'uri> {
         self.fragment.as_ref()
     }
 
+    /// Returns a mutable reference to the fragment of the URI reference.
+    ///
+    /// Since the fragment has no effect on whether the other components are valid, it can be
+    /// changed or removed freely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Fragment, URIReference};
+    ///
+    /// let mut reference = URIReference::try_from("http://example.com/path#fragment").unwrap();
+    /// *reference.fragment_mut() = Some(Fragment::try_from("other").unwrap());
+    /// assert_eq!(reference.fragment().unwrap(), "other");
+    /// ```
+    pub fn fragment_mut(&mut self) -> &mut Option<Fragment<'uri>> {
+        &mut self.fragment
+    }
+
     /// Returns whether the URI reference has an authority component.
     ///
     /// # Examples
@@ -224,6 +306,16 @@ impl<'uri> URIReference<'uri> {
         self.authority.is_some()
     }
 
+    /// Returns whether the URI reference has a port that is equal to the default port of its scheme.
+    fn has_default_port(&self) -> bool {
+        match (&self.scheme, &self.authority) {
+            (Some(scheme), Some(authority)) => {
+                authority.port().is_some() && authority.port() == scheme.default_port()
+            }
+            _ => false,
+        }
+    }
+
     /// Returns whether the URI reference has a fragment component.
     ///
     /// # Examples
@@ -370,6 +462,32 @@ impl<'uri> URIReference<'uri> {
         }
     }
 
+    /// Returns the string representation of the host, if present, of the URI reference.
+    ///
+    /// This is the same as formatting [`URIReference::host`], so IP literals are enclosed in
+    /// brackets. Registered names are borrowed, while IP addresses have to be formatted into a new
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let reference = URIReference::try_from("http://username@example.com").unwrap();
+    /// assert_eq!(reference.host_str().unwrap(), "example.com");
+    ///
+    /// let reference = URIReference::try_from("http://[::1]:8080").unwrap();
+    /// assert_eq!(reference.host_str().unwrap(), "[::1]");
+    /// ```
+    pub fn host_str(&self) -> Option<Cow<'_, str>> {
+        self.host().map(|host| match host {
+            Host::RegisteredName(name) => Cow::Borrowed(name.as_str()),
+            host => Cow::Owned(host.to_string()),
+        })
+    }
+
     /// Consumes the URI reference and converts it into a builder with the same values.
     ///
     /// # Examples
@@ -459,6 +577,28 @@ impl<'uri> URIReference<'uri> {
         )
     }
 
+    /// Returns whether the URI reference is absolute, i.e. it has a scheme.
+    ///
+    /// This is equivalent to [`URIReference::is_uri`]. Note that this says nothing about whether the
+    /// path is absolute, see [`Path::is_absolute`] for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let reference = URIReference::try_from("http://example.com/my/path").unwrap();
+    /// assert!(reference.is_absolute());
+    ///
+    /// let reference = URIReference::try_from("/my/path").unwrap();
+    /// assert!(!reference.is_absolute());
+    /// ```
+    pub fn is_absolute(&self) -> bool {
+        self.is_uri()
+    }
+
     /// Returns whether the URI reference is an absolute path reference.
     ///
     /// A URI reference is an absolute path reference if it is a relative reference that begins with
@@ -499,7 +639,9 @@ impl<'uri> URIReference<'uri> {
 
     /// Returns whether the URI reference is normalized.
     ///
-    /// A normalized URI reference will have all of its components normalized.
+    /// A normalized URI reference will have all of its components normalized. Furthermore, if the
+    /// URI reference has a scheme, the port must not be equal to the default port of the scheme
+    /// (see [`Scheme::default_port`]).
     ///
     /// # Examples
     ///
@@ -524,7 +666,7 @@ impl<'uri> URIReference<'uri> {
         }
 
         if let Some(authority) = self.authority.as_ref() {
-            if !authority.is_normalized() {
+            if !authority.is_normalized() || self.has_default_port() {
                 return false;
             }
         }
@@ -548,6 +690,27 @@ impl<'uri> URIReference<'uri> {
         true
     }
 
+    /// Returns whether the URI reference is relative, i.e. it has no scheme.
+    ///
+    /// This is equivalent to [`URIReference::is_relative_reference`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let reference = URIReference::try_from("//example.com/my/path").unwrap();
+    /// assert!(reference.is_relative());
+    ///
+    /// let reference = URIReference::try_from("http://example.com/my/path").unwrap();
+    /// assert!(!reference.is_relative());
+    /// ```
+    pub fn is_relative(&self) -> bool {
+        self.is_relative_reference()
+    }
+
     /// Returns whether the URI reference is a relative path reference.
     ///
     /// A URI reference is a relative path reference if it is a relative reference that does not
@@ -731,7 +894,9 @@ impl<'uri> URIReference<'uri> {
 
     /// Normalizes the URI reference.
     ///
-    /// A normalized URI reference will have all of its components normalized.
+    /// A normalized URI reference will have all of its components normalized. Furthermore, if the
+    /// URI reference has a scheme, a port that is equal to the default port of the scheme will be
+    /// removed (e.g. `"http://example.com:80/"` will become `"http://example.com/"`).
     ///
     /// # Examples
     ///
@@ -754,6 +919,10 @@ impl<'uri> URIReference<'uri> {
             scheme.normalize();
         }
 
+        if self.has_default_port() {
+            self.authority.as_mut().unwrap().set_port(None);
+        }
+
         if let Some(authority) = self.authority.as_mut() {
             authority.normalize();
         }
@@ -785,6 +954,30 @@ impl<'uri> URIReference<'uri> {
         &self.path
     }
 
+    /// Returns a mutable reference to the path of the URI reference.
+    ///
+    /// Some changes to the path could make it invalid in combination with the other components,
+    /// so a guard is returned instead of a plain reference. When the guard is dropped, the path is
+    /// adjusted in the same way as [`URIReferenceBuilder::fix_path`], without changing what the
+    /// URI reference resolves to (e.g. it is made absolute if there is an authority).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let mut reference = URIReference::try_from("http://example.com/my/path").unwrap();
+    /// reference.path_mut().push("other").unwrap();
+    /// assert_eq!(reference.path(), "/my/path/other");
+    /// ```
+    pub fn path_mut(&mut self) -> PathMut<'_, 'uri> {
+        PathMut {
+            uri_reference: self,
+        }
+    }
+
     /// Returns the password, if present, of the URI reference.
     ///
     /// Usage of a password in URI and URI references is deprecated.
@@ -843,6 +1036,26 @@ impl<'uri> URIReference<'uri> {
         self.query.as_ref()
     }
 
+    /// Returns a mutable reference to the query of the URI reference.
+    ///
+    /// Since the query has no effect on whether the other components are valid, it can be changed
+    /// or removed freely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let mut reference = URIReference::try_from("http://example.com/path?b=2&a=1").unwrap();
+    /// reference.query_mut().as_mut().unwrap().sort_pairs();
+    /// assert_eq!(reference.query().unwrap(), "a=1&b=2");
+    /// ```
+    pub fn query_mut(&mut self) -> &mut Option<Query<'uri>> {
+        &mut self.query
+    }
+
     /// Returns the scheme, if present, of the URI reference.
     ///
     /// # Examples
@@ -1052,6 +1265,50 @@ impl<'uri> URIReference<'uri> {
             None
         }
     }
+
+    /// Consumes the URI reference and returns it without its fragment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let reference = URIReference::try_from("http://example.com/path#fragment").unwrap();
+    /// assert_eq!(reference.without_fragment().to_string(), "http://example.com/path");
+    /// ```
+    pub fn without_fragment(mut self) -> Self {
+        self.fragment = None;
+        self
+    }
+
+    /// Appends the URI reference to the given string.
+    ///
+    /// The URI reference is formatted with [`Display`] directly into the string, so no intermediate
+    /// string is allocated. This is useful when serializing many URI references into a shared
+    /// buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::URIReference;
+    ///
+    /// let mut string = String::new();
+    ///
+    /// for value in &["http://example.com/a", "../b?query"] {
+    ///     URIReference::try_from(*value).unwrap().write_to(&mut string);
+    ///     string.push('\n');
+    /// }
+    ///
+    /// assert_eq!(string, "http://example.com/a\n../b?query\n");
+    /// ```
+    pub fn write_to(&self, out: &mut String) {
+        // Writing to a string cannot fail.
+        write!(out, "{}", self).unwrap();
+    }
 }
 
 impl Display for URIReference<'_> {
@@ -1063,10 +1320,10 @@ impl Display for URIReference<'_> {
 
         if let Some(ref authority) = self.authority {
             formatter.write_str("//")?;
-            formatter.write_str(&authority.to_string())?;
+            authority.fmt(formatter)?;
         }
 
-        formatter.write_str(&self.path.to_string())?;
+        self.path.fmt(formatter)?;
 
         if let Some(ref query) = self.query {
             formatter.write_char('?')?;
@@ -1084,59 +1341,45 @@ impl Display for URIReference<'_> {
 
 impl<'uri> From<URIReference<'uri>> for String {
     fn from(value: URIReference<'uri>) -> Self {
-        value.to_string()
+        let mut string = String::new();
+        value.write_to(&mut string);
+        string
     }
 }
 
-impl<'uri> TryFrom<&'uri [u8]> for URIReference<'uri> {
-    type Error = URIReferenceError;
+impl FromStr for URIReference<'static> {
+    type Err = URIReferenceError;
 
-    fn try_from(value: &'uri [u8]) -> Result<Self, Self::Error> {
-        let (scheme, value) = match parse_scheme(value) {
-            Ok((scheme, rest)) => {
-                if rest.starts_with(b":") {
-                    (Some(scheme), &rest[1..])
-                } else {
-                    (None, value)
-                }
-            }
-            _ => (None, value),
-        };
-
-        let (authority, value) = match value.get(0..2) {
-            Some(b"//") => {
-                let (authority, value) = parse_authority(&value[2..])?;
-                (Some(authority), value)
-            }
-            _ => (None, value),
-        };
-
-        let (mut path, value) = parse_path(value)?;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(URIReference::try_from(value.as_bytes())?.into_owned())
+    }
+}
 
-        if authority.is_some() {
-            path.set_absolute(true);
-        }
+/// URI references are ordered by their components in the order they appear, that is by scheme,
+/// authority, path, query, and then fragment. Absent components are ordered before present ones.
+impl Ord for URIReference<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.scheme
+            .cmp(&other.scheme)
+            .then_with(|| self.authority.cmp(&other.authority))
+            .then_with(|| self.path.cmp(&other.path))
+            .then_with(|| self.query.cmp(&other.query))
+            .then_with(|| self.fragment.cmp(&other.fragment))
+    }
+}
 
-        let (query, value) = if value.starts_with(b"?") {
-            let (query, value) = parse_query(&value[1..])?;
-            (Some(query), value)
-        } else {
-            (None, value)
-        };
+impl PartialOrd for URIReference<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-        let fragment = if value.starts_with(b"#") {
-            Some(Fragment::try_from(&value[1..])?)
-        } else {
-            None
-        };
+impl<'uri> TryFrom<&'uri [u8]> for URIReference<'uri> {
+    type Error = URIReferenceError;
 
-        Ok(URIReference {
-            authority,
-            fragment,
-            path,
-            query,
-            scheme,
-        })
+    fn try_from(value: &'uri [u8]) -> Result<Self, Self::Error> {
+        let (uri_reference, _) = parse_uri_reference(value).map_err(|(error, _)| error)?;
+        Ok(uri_reference)
     }
 }
 
@@ -1238,6 +1481,40 @@ impl<'uri> URIReferenceBuilder<'uri> {
         URIReference::from_parts(self.scheme, self.authority, path, self.query, self.fragment)
     }
 
+    /// Adjusts the path so that it can be combined with the authority and scheme that are currently
+    /// set, without changing what the URI reference resolves to.
+    ///
+    /// Building fails for some combinations of the path with the other components. This function
+    /// fixes them as follows:
+    ///  - If an authority is set, the path is made absolute.
+    ///  - If no authority is set and the path starts with `"//"`, it is prefixed with `"/."` since
+    ///    it would otherwise be interpreted as an authority.
+    ///  - If no authority is set and a relative path starts with an empty segment followed by other
+    ///    segments, it is prefixed with `"./"` since it would otherwise be interpreted as absolute.
+    ///  - If neither a scheme nor an authority is set and the first segment of a relative path
+    ///    contains a `':'`, the path is prefixed with `"./"` since it would otherwise be
+    ///    interpreted as a scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{Path, URIReferenceBuilder};
+    ///
+    /// let mut builder = URIReferenceBuilder::new();
+    /// builder.path(Path::try_from("my:path").unwrap()).fix_path();
+    /// let reference = builder.build().unwrap();
+    /// assert_eq!(reference.to_string(), "./my:path");
+    /// ```
+    pub fn fix_path(&mut self) -> &mut Self {
+        if let Some(path) = &mut self.path {
+            fix_path(self.scheme.as_ref(), self.authority.as_ref(), path);
+        }
+
+        self
+    }
+
     /// Sets the fragment part of the URI reference.
     ///
     /// It is optional to specify a fragment.
@@ -1366,7 +1643,7 @@ impl<'uri> URIReferenceBuilder<'uri> {
         AuthorityError: From<TAuthorityError>,
     {
         self.authority = match authority {
-            Some(authority) => Some(Authority::try_from(authority).map_err(|error| error)?),
+            Some(authority) => Some(Authority::try_from(authority)?),
             None => None,
         };
         Ok(self)
@@ -1402,7 +1679,7 @@ impl<'uri> URIReferenceBuilder<'uri> {
         FragmentError: From<TFragmentError>,
     {
         self.fragment = match fragment {
-            Some(fragment) => Some(Fragment::try_from(fragment).map_err(|error| error)?),
+            Some(fragment) => Some(Fragment::try_from(fragment)?),
             None => None,
         };
         Ok(self)
@@ -1432,7 +1709,7 @@ impl<'uri> URIReferenceBuilder<'uri> {
         Path<'uri>: TryFrom<TPath, Error = TPathError>,
         PathError: From<TPathError>,
     {
-        self.path = Some(Path::try_from(path).map_err(|error| error)?);
+        self.path = Some(Path::try_from(path)?);
         Ok(self)
     }
 
@@ -1465,7 +1742,7 @@ impl<'uri> URIReferenceBuilder<'uri> {
         QueryError: From<TQueryError>,
     {
         self.query = match query {
-            Some(query) => Some(Query::try_from(query).map_err(|error| error)?),
+            Some(query) => Some(Query::try_from(query)?),
             None => None,
         };
         Ok(self)
@@ -1500,7 +1777,7 @@ impl<'uri> URIReferenceBuilder<'uri> {
         SchemeError: From<TSchemeError>,
     {
         self.scheme = match scheme {
-            Some(scheme) => Some(Scheme::try_from(scheme).map_err(|error| error)?),
+            Some(scheme) => Some(Scheme::try_from(scheme)?),
             None => None,
         };
         Ok(self)
@@ -1662,6 +1939,44 @@ pub enum URIReferenceError {
     SchemelessPathStartsWithColonSegment,
 }
 
+impl URIReferenceError {
+    /// Returns the kind of this error, without any nested component error.
+    ///
+    /// Unlike [`URIReferenceError`] itself, the returned [`URIReferenceErrorKind`] is a plain
+    /// discriminant, which makes it convenient to match on or to store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    ///
+    /// use uriparse::{URIReference, URIReferenceErrorKind};
+    ///
+    /// assert_eq!(
+    ///     URIReference::try_from("/#a b").unwrap_err().kind(),
+    ///     URIReferenceErrorKind::Fragment
+    /// );
+    /// ```
+    pub fn kind(&self) -> URIReferenceErrorKind {
+        use self::URIReferenceError::*;
+
+        match self {
+            AbsolutePathStartsWithTwoSlashes => {
+                URIReferenceErrorKind::AbsolutePathStartsWithTwoSlashes
+            }
+            Authority(_) => URIReferenceErrorKind::Authority,
+            Fragment(_) => URIReferenceErrorKind::Fragment,
+            MissingPath => URIReferenceErrorKind::MissingPath,
+            Path(_) => URIReferenceErrorKind::Path,
+            Query(_) => URIReferenceErrorKind::Query,
+            Scheme(_) => URIReferenceErrorKind::Scheme,
+            SchemelessPathStartsWithColonSegment => {
+                URIReferenceErrorKind::SchemelessPathStartsWithColonSegment
+            }
+        }
+    }
+}
+
 impl Display for URIReferenceError {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         use self::URIReferenceError::*;
@@ -1685,8 +2000,39 @@ impl Display for URIReferenceError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for URIReferenceError {}
 
+/// The kind of an error representing an invalid URI reference, as returned by
+/// [`URIReferenceError::kind`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum URIReferenceErrorKind {
+    /// See [`URIReferenceError::AbsolutePathStartsWithTwoSlashes`].
+    AbsolutePathStartsWithTwoSlashes,
+
+    /// See [`URIReferenceError::Authority`].
+    Authority,
+
+    /// See [`URIReferenceError::Fragment`].
+    Fragment,
+
+    /// See [`URIReferenceError::MissingPath`].
+    MissingPath,
+
+    /// See [`URIReferenceError::Path`].
+    Path,
+
+    /// See [`URIReferenceError::Query`].
+    Query,
+
+    /// See [`URIReferenceError::Scheme`].
+    Scheme,
+
+    /// See [`URIReferenceError::SchemelessPathStartsWithColonSegment`].
+    SchemelessPathStartsWithColonSegment,
+}
+
 impl From<Infallible> for URIReferenceError {
     fn from(_: Infallible) -> Self {
         URIReferenceError::AbsolutePathStartsWithTwoSlashes
@@ -1723,6 +2069,106 @@ impl From<SchemeError> for URIReferenceError {
     }
 }
 
+/// Adjusts the path so that it is valid in combination with the given scheme and authority.
+///
+/// See [`URIReferenceBuilder::fix_path`] for the adjustments made.
+fn fix_path(scheme: Option<&Scheme<'_>>, authority: Option<&Authority<'_>>, path: &mut Path<'_>) {
+    let first_segment = &path.segments()[0];
+
+    if authority.is_some() {
+        path.set_absolute(true);
+    } else if path.is_absolute() && first_segment.is_empty() && path.segments().len() > 1 {
+        *path = Path::try_from(format!("/.{}", path).as_str())
+            .unwrap()
+            .into_owned();
+    } else if path.is_relative()
+        && ((first_segment.is_empty() && path.segments().len() > 1)
+            || (scheme.is_none() && first_segment.contains(':')))
+    {
+        *path = Path::try_from(format!("./{}", path).as_str())
+            .unwrap()
+            .into_owned();
+    }
+}
+
+/// Parses the URI reference from the given byte string, along with the spans of its components
+/// within the byte string.
+///
+/// On failure, the index of the byte at which the error occurred is returned along with the error.
+pub(crate) fn parse_uri_reference(
+    value: &[u8],
+) -> Result<(URIReference<'_>, URISpans), (URIReferenceError, usize)> {
+    let length = value.len();
+    let offset = |rest: &[u8]| length - rest.len();
+
+    let (scheme, scheme_span, value) = match parse_scheme(value) {
+        Ok((scheme, rest)) => {
+            if rest.starts_with(b":") {
+                (Some(scheme), Some(0..offset(rest)), &rest[1..])
+            } else {
+                (None, None, value)
+            }
+        }
+        _ => (None, None, value),
+    };
+
+    let (authority, authority_span, value) = match value.get(0..2) {
+        Some(b"//") => {
+            let (authority, rest) = parse_authority(&value[2..])
+                .map_err(|(error, index)| (error.into(), offset(value) + 2 + index))?;
+            (Some(authority), Some(offset(value) + 2..offset(rest)), rest)
+        }
+        _ => (None, None, value),
+    };
+
+    let (mut path, rest) =
+        parse_path(value).map_err(|(error, index)| (error.into(), offset(value) + index))?;
+    let path_span = offset(value)..offset(rest);
+    let value = rest;
+
+    if authority.is_some() {
+        path.set_absolute(true);
+    }
+
+    let (query, query_span, value) = if value.starts_with(b"?") {
+        let (query, rest) = parse_query(&value[1..])
+            .map_err(|(error, index)| (error.into(), offset(value) + 1 + index))?;
+        (Some(query), Some(offset(value) + 1..offset(rest)), rest)
+    } else {
+        (None, None, value)
+    };
+
+    let (fragment, fragment_span) = if value.starts_with(b"#") {
+        let fragment = Fragment::try_from(&value[1..]).map_err(|error| {
+            let index = match error {
+                FragmentError::InvalidCharacter { index, .. }
+                | FragmentError::InvalidPercentEncoding { index } => index,
+            };
+            (error.into(), offset(value) + 1 + index)
+        })?;
+        (Some(fragment), Some(offset(value) + 1..length))
+    } else {
+        (None, None)
+    };
+
+    let uri_reference = URIReference {
+        authority,
+        fragment,
+        path,
+        query,
+        scheme,
+    };
+    let spans = URISpans {
+        authority: authority_span,
+        fragment: fragment_span,
+        path: path_span,
+        query: query_span,
+        scheme: scheme_span,
+    };
+
+    Ok((uri_reference, spans))
+}
+
 fn validate_absolute_path(
     authority: Option<&Authority>,
     path: &Path,
@@ -1745,6 +2191,7 @@ fn validate_schemeless_path(
 ) -> Result<(), URIReferenceError> {
     if scheme.is_some()
         || authority.is_some()
+        || path.is_absolute()
         || !path
             .segments()
             .first()
@@ -1758,9 +2205,264 @@ fn validate_schemeless_path(
     }
 }
 
+/// A component of a URI reference, as yielded by [`URIReference::components`].
+///
+/// Formatting a component produces the same output as formatting the component itself, so the
+/// delimiters (e.g. the `'?'` before the query) are not included.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Component<'component> {
+    /// The authority component.
+    Authority(&'component Authority<'component>),
+
+    /// The fragment component.
+    Fragment(&'component Fragment<'component>),
+
+    /// The path component.
+    Path(&'component Path<'component>),
+
+    /// The query component.
+    Query(&'component Query<'component>),
+
+    /// The scheme component.
+    Scheme(&'component Scheme<'component>),
+}
+
+impl Display for Component<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Component::Authority(authority) => authority.fmt(formatter),
+            Component::Fragment(fragment) => fragment.fmt(formatter),
+            Component::Path(path) => path.fmt(formatter),
+            Component::Query(query) => query.fmt(formatter),
+            Component::Scheme(scheme) => scheme.fmt(formatter),
+        }
+    }
+}
+
+/// An iterator over the components that are present in a URI reference.
+///
+/// This is created by [`URIReference::components`], [`URI::components`], and
+/// [`RelativeReference::components`].
+///
+/// [`RelativeReference::components`]: crate::RelativeReference::components
+/// [`URI::components`]: crate::URI::components
+#[derive(Clone, Debug)]
+pub struct Components<'component> {
+    /// The index of the next component to check for, in the order they appear in the URI reference.
+    next: u8,
+
+    /// The URI reference whose components are iterated over.
+    uri_reference: &'component URIReference<'component>,
+}
+
+impl<'component> Iterator for Components<'component> {
+    type Item = Component<'component>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let uri_reference = self.uri_reference;
+
+        while self.next < 5 {
+            self.next += 1;
+
+            let component = match self.next {
+                1 => uri_reference.scheme().map(Component::Scheme),
+                2 => uri_reference.authority().map(Component::Authority),
+                3 => Some(Component::Path(uri_reference.path())),
+                4 => uri_reference.query().map(Component::Query),
+                _ => uri_reference.fragment().map(Component::Fragment),
+            };
+
+            if component.is_some() {
+                return component;
+            }
+        }
+
+        None
+    }
+}
+
+/// A guard giving mutable access to the path of a URI reference.
+///
+/// This is created by [`URIReference::path_mut`], [`URI::path_mut`], and
+/// [`RelativeReference::path_mut`]. When it is dropped, the path is adjusted so that it is valid
+/// in combination with the other components of the URI reference (see
+/// [`URIReferenceBuilder::fix_path`]).
+///
+/// [`RelativeReference::path_mut`]: crate::RelativeReference::path_mut
+/// [`URI::path_mut`]: crate::URI::path_mut
+#[derive(Debug)]
+pub struct PathMut<'reference, 'uri> {
+    /// The URI reference whose path is being changed.
+    uri_reference: &'reference mut URIReference<'uri>,
+}
+
+impl<'uri> Deref for PathMut<'_, 'uri> {
+    type Target = Path<'uri>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.uri_reference.path
+    }
+}
+
+impl DerefMut for PathMut<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.uri_reference.path
+    }
+}
+
+impl Drop for PathMut<'_, '_> {
+    fn drop(&mut self) {
+        let uri_reference = &mut *self.uri_reference;
+        fix_path(
+            uri_reference.scheme.as_ref(),
+            uri_reference.authority.as_ref(),
+            &mut uri_reference.path,
+        );
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use alloc::string::ToString;
+
     use super::*;
+    use crate::authority::PortError;
+
+    #[test]
+    fn test_builder_fix_path() {
+        fn test_case(builder: &mut URIReferenceBuilder<'_>, expected: &str) {
+            builder.fix_path();
+            let reference = builder.clone().build().unwrap();
+            assert_eq!(reference.to_string(), expected);
+            assert_eq!(URIReference::try_from(expected).unwrap(), reference);
+        }
+
+        let path = |value| Path::try_from(value).unwrap();
+        let authority = Authority::try_from("example.com").unwrap();
+
+        test_case(
+            URIReferenceBuilder::new()
+                .authority(Some(authority.clone()))
+                .path(path("a/b")),
+            "//example.com/a/b",
+        );
+        test_case(URIReferenceBuilder::new().path(path("//a/b")), "/.//a/b");
+        test_case(URIReferenceBuilder::new().path(path("a:b/c")), "./a:b/c");
+        test_case(URIReferenceBuilder::new().path(path("a/b:c")), "a/b:c");
+        test_case(URIReferenceBuilder::new().path(path("/a:b")), "/a:b");
+        test_case(
+            URIReferenceBuilder::new()
+                .scheme(Some(Scheme::HTTP))
+                .path(path("a:b")),
+            "http:a:b",
+        );
+        test_case(URIReferenceBuilder::new().path(path("")), "");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        fn test_case(value: &str, has_authority: bool) {
+            let reference = URIReference::try_from(value).unwrap();
+            assert_eq!(reference.has_authority(), has_authority);
+
+            let displayed = reference.to_string();
+            assert_eq!(displayed, value);
+            assert_eq!(URIReference::try_from(displayed.as_str()), Ok(reference));
+        }
+
+        test_case("//cdn.example.com/lib.js", true);
+        test_case("//host/path", true);
+        test_case("//host/path?q#f", true);
+        test_case("//user@[::1]:8080/", true);
+        test_case("///path", true);
+        test_case("/path", false);
+        test_case("/.//path", false);
+        test_case("path", false);
+        test_case("./a:b", false);
+        test_case("?q", false);
+        test_case("#f", false);
+        test_case("", false);
+
+        // An authority is always followed by an absolute path.
+        let reference = URIReference::try_from("//host").unwrap();
+        assert_eq!(reference.to_string(), "//host/");
+        assert!(reference.has_authority());
+        assert_eq!(reference.host().unwrap().to_string(), "host");
+    }
+
+    #[test]
+    fn test_error_kind() {
+        use std::collections::HashSet;
+
+        let errors = [
+            URIReferenceError::AbsolutePathStartsWithTwoSlashes,
+            URIReferenceError::Authority(AuthorityError::Port(PortError::InvalidCharacter)),
+            URIReferenceError::Fragment(FragmentError::InvalidPercentEncoding { index: 0 }),
+            URIReferenceError::MissingPath,
+            URIReferenceError::Path(PathError::InvalidCharacter),
+            URIReferenceError::Query(QueryError::InvalidCharacter),
+            URIReferenceError::Scheme(SchemeError::StartsWithNonAlphabetic),
+            URIReferenceError::SchemelessPathStartsWithColonSegment,
+        ];
+        let kinds: HashSet<URIReferenceErrorKind> =
+            errors.iter().map(URIReferenceError::kind).collect();
+        assert_eq!(kinds.len(), errors.len());
+
+        assert_eq!(
+            URIReference::try_from("?a b").unwrap_err().kind(),
+            URIReferenceErrorKind::Query
+        );
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let reference = URIReference::from_parts(
+            None::<Scheme>,
+            None::<Authority>,
+            "/a:b",
+            None::<Query>,
+            None::<Fragment>,
+        )
+        .unwrap();
+        assert_eq!(reference.to_string(), "/a:b");
+
+        assert_eq!(
+            URIReference::from_parts(
+                None::<Scheme>,
+                None::<Authority>,
+                "a:b",
+                None::<Query>,
+                None::<Fragment>
+            ),
+            Err(URIReferenceError::SchemelessPathStartsWithColonSegment)
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let reference: URIReference<'static> = "http://example.com/path".parse().unwrap();
+        assert_eq!(reference.to_string(), "http://example.com/path");
+
+        let reference = {
+            let value = String::from("//example.com/path?query");
+            value.parse::<URIReference>().unwrap()
+        };
+        assert!(reference.is_network_path_reference());
+
+        assert_eq!(
+            "a:b:c/%".parse::<URIReference>(),
+            Err(URIReferenceError::Path(PathError::InvalidPercentEncoding))
+        );
+        assert_eq!(
+            "http://example.com/#a b".parse::<URIReference>(),
+            Err(URIReferenceError::Fragment(
+                FragmentError::InvalidCharacter {
+                    byte: b' ',
+                    index: 1,
+                }
+            ))
+        );
+    }
 
     #[test]
     fn test_parse_uri_reference() {
@@ -1896,4 +2598,72 @@ mod test {
         .unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_predicates() {
+        let reference = URIReference::try_from("//host/path").unwrap();
+        assert!(reference.is_relative());
+        assert!(!reference.is_absolute());
+        assert!(reference.is_network_path_reference());
+        assert!(!reference.has_scheme());
+        assert!(reference.has_authority());
+        assert!(!reference.has_query());
+        assert!(!reference.has_fragment());
+
+        let reference = URIReference::try_from("/path?query").unwrap();
+        assert!(reference.is_relative());
+        assert!(!reference.is_absolute());
+        assert!(reference.is_absolute_path_reference());
+        assert!(!reference.has_scheme());
+        assert!(!reference.has_authority());
+        assert!(reference.has_query());
+        assert!(!reference.has_fragment());
+
+        let reference = URIReference::try_from("path#fragment").unwrap();
+        assert!(reference.is_relative());
+        assert!(!reference.is_absolute());
+        assert!(reference.is_relative_path_reference());
+        assert!(!reference.has_scheme());
+        assert!(!reference.has_authority());
+        assert!(!reference.has_query());
+        assert!(reference.has_fragment());
+
+        let reference = URIReference::try_from("http://host/path?query#fragment").unwrap();
+        assert!(!reference.is_relative());
+        assert!(reference.is_absolute());
+        assert!(reference.is_uri());
+        assert!(reference.has_scheme());
+        assert!(reference.has_authority());
+        assert!(reference.has_query());
+        assert!(reference.has_fragment());
+    }
+
+    #[test]
+    fn test_empty_query() {
+        fn test_case(value: &str, expected: Option<&str>) {
+            let reference = URIReference::try_from(value).unwrap();
+            assert_eq!(reference.query().map(Query::as_str), expected);
+            assert_eq!(reference.has_query(), expected.is_some());
+
+            let round_trip = URIReference::try_from(&*reference.to_string())
+                .unwrap()
+                .into_owned();
+            assert_eq!(round_trip.query().map(Query::as_str), expected);
+        }
+
+        test_case("http://x", None);
+        test_case("http://x?", Some(""));
+        test_case("http://x?#", Some(""));
+        test_case("http://x?a=b", Some("a=b"));
+        test_case("/path?", Some(""));
+        test_case("?", Some(""));
+        test_case("", None);
+
+        let reference = URIReference::try_from("http://x?").unwrap();
+        assert!(reference.query().unwrap().is_empty());
+        assert_eq!(reference.to_string(), "http://x/?");
+
+        let reference = URIReference::try_from("http://x").unwrap();
+        assert_eq!(reference.to_string(), "http://x/");
+    }
 }
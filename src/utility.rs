@@ -1,4 +1,13 @@
-use std::hash::{Hash, Hasher};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::slice;
+use core::str;
+#[cfg(feature = "std")]
+use std::error::Error;
 
 #[rustfmt::skip]
 pub const UNRESERVED_CHAR_MAP: [u8; 256] = [
@@ -21,6 +30,125 @@ pub const UNRESERVED_CHAR_MAP: [u8; 256] = [
     0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0,    0, // F
 ];
 
+/// Decodes all percent-encoded bytes in the given string.
+///
+/// The string is assumed to only contain valid percent-encodings. If the decoded bytes are not valid
+/// UTF-8, invalid sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+pub fn decode_percent_encoded(value: &str) -> Cow<'_, str> {
+    match percent_decode(value.as_bytes()).unwrap() {
+        Cow::Borrowed(_) => Cow::Borrowed(value),
+        Cow::Owned(decoded) => match String::from_utf8(decoded) {
+            Ok(decoded) => Cow::Owned(decoded),
+            Err(error) => Cow::Owned(String::from_utf8_lossy(error.as_bytes()).into_owned()),
+        },
+    }
+}
+
+/// Decodes all percent-encoded bytes in the given byte string.
+///
+/// If the byte string contains no `'%'` characters, the returned value will borrow from the input.
+/// An error will be returned if a `'%'` is not followed by two hexadecimal digits.
+///
+/// # Examples
+///
+/// ```
+/// use uriparse::percent_decode;
+///
+/// assert_eq!(percent_decode(b"a%20b").unwrap(), &b"a b"[..]);
+/// assert_eq!(percent_decode(b"%C3%A9").unwrap(), "\u{e9}".as_bytes());
+/// assert_eq!(percent_decode(b"a%2").unwrap_err().index(), 1);
+/// ```
+pub fn percent_decode(value: &[u8]) -> Result<Cow<'_, [u8]>, InvalidPercentEncoding> {
+    let index = match value.iter().position(|&byte| byte == b'%') {
+        Some(index) => index,
+        None => return Ok(Cow::Borrowed(value)),
+    };
+
+    let mut decoded = Vec::with_capacity(value.len());
+    decoded.extend_from_slice(&value[..index]);
+
+    let mut bytes = value[index..].iter().cloned().enumerate();
+
+    while let Some((offset, byte)) = bytes.next() {
+        if byte == b'%' {
+            let (hex_value, _) = get_percent_encoded_value(
+                bytes.next().map(|(_, byte)| byte),
+                bytes.next().map(|(_, byte)| byte),
+            )
+            .map_err(|_| InvalidPercentEncoding {
+                index: index + offset,
+            })?;
+            decoded.push(hex_value);
+        } else {
+            decoded.push(byte);
+        }
+    }
+
+    Ok(Cow::Owned(decoded))
+}
+
+/// Percent-encodes all bytes in the given string that are not allowed by the given character map.
+///
+/// The `'%'` character is always percent-encoded. Percent-encodings are always uppercase.
+pub fn encode_percent_encoded(value: &str, char_map: &[u8; 256]) -> String {
+    percent_encode(value.as_bytes(), |byte| {
+        byte == b'%' || char_map[byte as usize] == 0
+    })
+    .into_owned()
+}
+
+/// Percent-encodes all bytes in the given byte string for which `should_encode` returns `true`.
+///
+/// Bytes that are not ASCII are always percent-encoded, so the result is always valid ASCII.
+/// Percent-encodings are always uppercase. If no bytes need to be encoded, the returned value will
+/// borrow from the input.
+///
+/// Note that `'%'` is only encoded if `should_encode` says so. Unless the input is known not to
+/// contain percent-encodings already, it should be encoded so that the result can be decoded back
+/// into the original input.
+///
+/// # Examples
+///
+/// ```
+/// use uriparse::percent_encode;
+///
+/// let encoded = percent_encode(b"a b/c", |byte| byte == b' ' || byte == b'/');
+/// assert_eq!(encoded, "a%20b%2Fc");
+///
+/// let encoded = percent_encode("caf\u{e9}".as_bytes(), |byte| !byte.is_ascii_alphanumeric());
+/// assert_eq!(encoded, "caf%C3%A9");
+/// ```
+pub fn percent_encode<TShouldEncode>(value: &[u8], should_encode: TShouldEncode) -> Cow<'_, str>
+where
+    TShouldEncode: Fn(u8) -> bool,
+{
+    const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+    let needs_encoding = |byte: u8| !byte.is_ascii() || should_encode(byte);
+
+    let index = match value.iter().position(|&byte| needs_encoding(byte)) {
+        Some(index) => index,
+        // Unsafe: All bytes were checked to be ASCII.
+        None => return Cow::Borrowed(unsafe { str::from_utf8_unchecked(value) }),
+    };
+
+    let mut encoded = String::with_capacity(value.len() + 2 * (value.len() - index));
+    // Unsafe: All bytes before the index were checked to be ASCII.
+    encoded.push_str(unsafe { str::from_utf8_unchecked(&value[..index]) });
+
+    for &byte in &value[index..] {
+        if needs_encoding(byte) {
+            encoded.push('%');
+            encoded.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+            encoded.push(HEX_DIGITS[(byte & 0x0F) as usize] as char);
+        } else {
+            encoded.push(byte as char);
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
 pub fn get_percent_encoded_value(
     first_digit: Option<u8>,
     second_digit: Option<u8>,
@@ -39,14 +167,23 @@ pub fn get_percent_encoded_value(
 
 fn hex_digit_to_decimal(digit: u8) -> Result<(u8, bool), ()> {
     match digit {
-        _ if digit >= b'A' && digit <= b'F' => Ok((digit - b'A' + 10, true)),
-        _ if digit >= b'a' && digit <= b'f' => Ok((digit - b'a' + 10, false)),
+        _ if (b'A'..=b'F').contains(&digit) => Ok((digit - b'A' + 10, true)),
+        _ if (b'a'..=b'f').contains(&digit) => Ok((digit - b'a' + 10, false)),
         _ if digit.is_ascii_digit() => Ok((digit - b'0', true)),
         _ => Err(()),
     }
 }
 
-/// This function is unsafe because it makes the assumption that the given string is valid ASCII-US.
+/// Normalizes the percent-encoded string in-place, decoding percent-encoded bytes that are in the
+/// unreserved character set and uppercasing the hexadecimal digits of the remaining ones.
+///
+/// Decoding shrinks a percent-encoding from three bytes to one and everything else keeps its
+/// length, so the write index never overtakes the read index and no unread byte is overwritten.
+/// The string is truncated to the written length afterwards.
+///
+/// This function is unsafe because it makes the assumption that the given string is valid ASCII-US
+/// with only valid percent-encodings. Since decoded unreserved bytes are also ASCII-US, the string
+/// remains valid UTF-8.
 pub unsafe fn normalize_string(string: &mut String, case_sensitive: bool) {
     let bytes = string.as_mut_vec();
     let mut read_index = 0;
@@ -63,7 +200,11 @@ pub unsafe fn normalize_string(string: &mut String, case_sensitive: bool) {
             read_index += 2;
 
             if UNRESERVED_CHAR_MAP[hex_value as usize] != 0 {
-                bytes[write_index] = hex_value;
+                bytes[write_index] = if case_sensitive {
+                    hex_value
+                } else {
+                    hex_value.to_ascii_lowercase()
+                };
                 write_index += 1;
             } else {
                 bytes[write_index] = b'%';
@@ -124,79 +265,167 @@ where
     length.hash(state);
 }
 
-fn percent_encoded_equality_helper(
-    byte: u8,
-    first_digit: Option<u8>,
-    second_digit: Option<u8>,
+/// An iterator over the bytes of a percent-encoded byte string as if it were normalized.
+///
+/// Percent-encoded bytes in the unreserved character set are yielded decoded, and all other
+/// percent-encodings are yielded with uppercase hexadecimal digits. If the iterator is not case
+/// sensitive, all other bytes are yielded lowercase.
+///
+/// The byte string is assumed to only contain valid percent-encodings.
+struct NormalizedBytes<'bytes> {
+    bytes: slice::Iter<'bytes, u8>,
     case_sensitive: bool,
-) -> bool {
-    if UNRESERVED_CHAR_MAP[byte as usize] == 0 {
-        return false;
-    }
+    pending: [u8; 2],
+    pending_index: usize,
+}
 
-    match get_percent_encoded_value(first_digit, second_digit) {
-        Ok((hex_value, _)) => {
-            if case_sensitive {
-                hex_value == byte
-            } else {
-                hex_value.eq_ignore_ascii_case(&byte)
-            }
+impl<'bytes> NormalizedBytes<'bytes> {
+    fn new(value: &'bytes [u8], case_sensitive: bool) -> Self {
+        NormalizedBytes {
+            bytes: value.iter(),
+            case_sensitive,
+            pending: [0; 2],
+            pending_index: 2,
         }
-        Err(_) => false,
     }
 }
 
-pub fn percent_encoded_equality(left: &[u8], right: &[u8], case_sensitive: bool) -> bool {
-    let mut left_bytes = left.iter();
-    let mut right_bytes = right.iter();
-
-    loop {
-        match (left_bytes.next(), right_bytes.next()) {
-            (Some(b'%'), Some(b'%')) => (),
-            (Some(b'%'), Some(&right_byte)) => {
-                if !percent_encoded_equality_helper(
-                    right_byte,
-                    left_bytes.next().cloned(),
-                    left_bytes.next().cloned(),
-                    case_sensitive,
-                ) {
-                    return false;
-                }
-            }
-            (Some(&left_byte), Some(b'%')) => {
-                if !percent_encoded_equality_helper(
-                    left_byte,
-                    right_bytes.next().cloned(),
-                    right_bytes.next().cloned(),
-                    case_sensitive,
-                ) {
-                    return false;
-                }
-            }
-            (Some(left_byte), Some(right_byte)) => {
-                let equal = if case_sensitive {
-                    left_byte == right_byte
-                } else {
-                    left_byte.eq_ignore_ascii_case(&right_byte)
-                };
+impl Iterator for NormalizedBytes<'_> {
+    type Item = u8;
 
-                if !equal {
-                    return false;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending_index < self.pending.len() {
+            let byte = self.pending[self.pending_index];
+            self.pending_index += 1;
+            return Some(byte);
+        }
+
+        let byte = match self.bytes.next() {
+            Some(b'%') => {
+                let first_digit = self.bytes.next().cloned();
+                let second_digit = self.bytes.next().cloned();
+                let (hex_value, _) = get_percent_encoded_value(first_digit, second_digit).unwrap();
+
+                if UNRESERVED_CHAR_MAP[hex_value as usize] == 0 {
+                    self.pending = [
+                        first_digit.unwrap().to_ascii_uppercase(),
+                        second_digit.unwrap().to_ascii_uppercase(),
+                    ];
+                    self.pending_index = 0;
+                    return Some(b'%');
                 }
+
+                hex_value
             }
-            (None, None) => return true,
-            _ => return false,
+            Some(&byte) => byte,
+            None => return None,
+        };
+
+        if self.case_sensitive {
+            Some(byte)
+        } else {
+            Some(byte.to_ascii_lowercase())
         }
     }
 }
 
+/// Compares two percent-encoded byte strings as if they were both normalized.
+///
+/// This ordering is consistent with [`percent_encoded_equality`] and does not allocate.
+pub fn percent_encoded_cmp(left: &[u8], right: &[u8], case_sensitive: bool) -> Ordering {
+    NormalizedBytes::new(left, case_sensitive).cmp(NormalizedBytes::new(right, case_sensitive))
+}
+
+/// Compares two percent-encoded byte strings as if they were both normalized.
+///
+/// Percent-encodings are decoded before comparing, and their hexadecimal digits are always
+/// compared case-insensitively, even if `case_sensitive` is `true` (e.g. `"%2f"` is equal to
+/// `"%2F"`). The `case_sensitive` flag only applies to the remaining bytes.
+pub fn percent_encoded_equality(left: &[u8], right: &[u8], case_sensitive: bool) -> bool {
+    NormalizedBytes::new(left, case_sensitive).eq(NormalizedBytes::new(right, case_sensitive))
+}
+
+/// Percent-decodes both byte strings and compares the decoded bytes in constant time.
+///
+/// The comparison only depends on the lengths of the decoded byte strings, not on their contents.
+/// The decoding itself is not constant-time. Both byte strings are assumed to only contain valid
+/// percent-encodings.
+pub fn percent_decoded_constant_time_equality(left: &[u8], right: &[u8]) -> bool {
+    let left = percent_decode(left).unwrap();
+    let right = percent_decode(right).unwrap();
+
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let difference = left
+        .iter()
+        .zip(right.iter())
+        .fold(0, |difference, (left, right)| difference | (left ^ right));
+    core::hint::black_box(difference) == 0
+}
+
+/// An error representing an invalid percent-encoding, i.e. a `'%'` that is not followed by two
+/// hexadecimal digits.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct InvalidPercentEncoding {
+    /// The index of the `'%'` starting the invalid percent-encoding.
+    index: usize,
+}
+
+impl InvalidPercentEncoding {
+    /// Returns the index of the `'%'` starting the invalid percent-encoding.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Display for InvalidPercentEncoding {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "invalid percent-encoding at index {}",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for InvalidPercentEncoding {}
+
 #[cfg(test)]
 mod test {
+    use alloc::string::ToString;
     use std::collections::hash_map::RandomState;
     use std::hash::BuildHasher;
 
     use super::*;
 
+    #[test]
+    fn test_cmp() {
+        // Case sensitive
+
+        assert_eq!(percent_encoded_cmp(b"abc", b"abc", true), Ordering::Equal);
+        assert_eq!(percent_encoded_cmp(b"abc", b"%61bc", true), Ordering::Equal);
+        assert_eq!(percent_encoded_cmp(b"%2f", b"%2F", true), Ordering::Equal);
+        assert_eq!(percent_encoded_cmp(b"abc", b"abd", true), Ordering::Less);
+        assert_eq!(
+            percent_encoded_cmp(b"ab%64", b"abc", true),
+            Ordering::Greater
+        );
+        assert_eq!(percent_encoded_cmp(b"ab", b"abc", true), Ordering::Less);
+        assert_eq!(percent_encoded_cmp(b"Abc", b"abc", true), Ordering::Less);
+
+        // Case insensitive
+
+        assert_eq!(percent_encoded_cmp(b"Abc", b"abc", false), Ordering::Equal);
+        assert_eq!(
+            percent_encoded_cmp(b"%41bc", b"abc", false),
+            Ordering::Equal
+        );
+        assert_eq!(percent_encoded_cmp(b"ABC", b"abd", false), Ordering::Less);
+    }
+
     #[test]
     fn test_equality() {
         // Case sensitive
@@ -222,6 +451,12 @@ mod test {
 
         assert!(!percent_encoded_equality(b"abc", b"xyz", false));
         assert!(!percent_encoded_equality(b"/", b"%2F", false));
+
+        // Differently cased percent-encodings
+
+        assert!(percent_encoded_equality(b"%4d", b"%4D", true));
+        assert!(percent_encoded_equality(b"%2f", b"%2F", true));
+        assert!(!percent_encoded_equality(b"%2F", b"%2E", true));
     }
 
     #[test]
@@ -275,4 +510,78 @@ mod test {
         assert!(!compare_hashes(b"abc", b"xyz", &state, false));
         assert!(!compare_hashes(b"/", b"%2F", &state, false));
     }
+
+    #[test]
+    fn test_normalize_string() {
+        fn test_case(value: &str, case_sensitive: bool, expected: &str) {
+            let mut string = value.to_string();
+            unsafe { normalize_string(&mut string, case_sensitive) };
+            assert_eq!(string, expected);
+            assert_eq!(string.len(), expected.len());
+        }
+
+        test_case("", true, "");
+        test_case("abc", true, "abc");
+        test_case("%41", true, "A");
+        test_case("%41%42%43", true, "ABC");
+        test_case("a%41b%42c%43d", true, "aAbBcCd");
+        test_case("%41/%2f/%42%2F", true, "A/%2F/B%2F");
+        test_case("%ff%41%fe%7e%7E", true, "%FFA%FE~~");
+        test_case("%2541", true, "%2541");
+        test_case("%41%42%43", false, "abc");
+        test_case("A%42c%44%2f", false, "abcd%2F");
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        use crate::fragment::FRAGMENT_CHAR_MAP;
+        use crate::path::PATH_CHAR_MAP;
+
+        let fragment_set = |byte| byte == b'%' || FRAGMENT_CHAR_MAP[byte as usize] == 0;
+        assert_eq!(percent_encode(b"a/b?c", fragment_set), "a/b?c");
+        assert_eq!(percent_encode(b"a b#c%", fragment_set), "a%20b%23c%25");
+        assert!(matches!(
+            percent_encode(b"fragment", fragment_set),
+            Cow::Borrowed("fragment")
+        ));
+
+        let segment_set = |byte| byte == b'%' || PATH_CHAR_MAP[byte as usize] == 0;
+        assert_eq!(percent_encode(b"a:b@c", segment_set), "a:b@c");
+        assert_eq!(percent_encode(b"a/b?c", segment_set), "a%2Fb%3Fc");
+
+        let alphanumeric_set = |byte: u8| !byte.is_ascii_alphanumeric();
+        assert_eq!(percent_encode(b"abc123", alphanumeric_set), "abc123");
+        assert_eq!(percent_encode(b"a-b.c~", alphanumeric_set), "a%2Db%2Ec%7E");
+        assert_eq!(
+            percent_encode("\u{2713}".as_bytes(), alphanumeric_set),
+            "%E2%9C%93"
+        );
+
+        assert_eq!(percent_encode(b"", |_| true), "");
+        assert_eq!(percent_encode(&[0xFF, b'a'], |_| false), "%FFa");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode(b"").unwrap(), &b""[..]);
+        assert_eq!(percent_decode(b"abc").unwrap(), &b"abc"[..]);
+        assert!(matches!(
+            percent_decode(b"abc").unwrap(),
+            Cow::Borrowed(b"abc")
+        ));
+        assert_eq!(percent_decode(b"a%20b").unwrap(), &b"a b"[..]);
+        assert_eq!(percent_decode(b"%41%42%43").unwrap(), &b"ABC"[..]);
+        assert_eq!(percent_decode(b"%2f%2F").unwrap(), &b"//"[..]);
+        assert_eq!(percent_decode(b"%FF%00").unwrap(), &[0xFF, 0x00][..]);
+        assert_eq!(percent_decode(b"%25%32%30").unwrap(), &b"%20"[..]);
+
+        assert_eq!(percent_decode(b"%ZZ").unwrap_err().index(), 0);
+        assert_eq!(percent_decode(b"ab%A").unwrap_err().index(), 2);
+        assert_eq!(percent_decode(b"a%20%").unwrap_err().index(), 4);
+        assert_eq!(percent_decode(b"%%41").unwrap_err().index(), 0);
+        assert_eq!(
+            percent_decode(b"%G1").unwrap_err().to_string(),
+            "invalid percent-encoding at index 0"
+        );
+    }
 }